@@ -6,7 +6,9 @@ use rp235x_hal as hal;
 
 // Import traits for embedded abstractions
 use embedded_hal::delay::DelayNs;
-use embedded_hal::digital::OutputPin;
+
+// LED wrapper
+use led::{ActiveLevel, Led};
 
 // Debugging output
 use defmt::*;
@@ -57,18 +59,21 @@ fn main() -> ! {
     );
 
     // Configure pin, get ownership of that pin
-    let mut led_pin = pins.gpio15.into_push_pull_output();
+    let led_pin = pins.gpio15.into_push_pull_output();
+
+    // LED from GPIO15 to ground: lit when the pin is high
+    let mut led = Led::new(led_pin, ActiveLevel::High);
 
     // Move ownership of TIMER0 peripheral to create Timer struct
     let mut timer = hal::Timer::new_timer0(pac.TIMER0, &mut pac.RESETS, &clocks);
 
     // Blink loop
     loop {
-        led_pin.set_high().unwrap();
-        debug!("LED on");
+        led.on().unwrap();
+        debug!("LED on: {}", led.is_on().unwrap());
         timer.delay_ms(500);
-        led_pin.set_low().unwrap();
-        debug!("LED off");
+        led.off().unwrap();
+        debug!("LED on: {}", led.is_on().unwrap());
         timer.delay_ms(500);
     }
 }
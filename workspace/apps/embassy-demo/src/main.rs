@@ -4,7 +4,8 @@
 // Embassy: HAL imports
 use embassy_rp::bind_interrupts;
 use embassy_rp::gpio;
-use embassy_rp::peripherals::USB;
+use embassy_rp::i2c;
+use embassy_rp::peripherals::{I2C1, USB};
 use embassy_rp::usb::{Driver, InterruptHandler};
 
 // Embassy: main executor
@@ -12,23 +13,38 @@ use embassy_executor::Spawner;
 
 // Embassy: futures
 use embassy_futures::join::join_array;
+use embassy_futures::select::{Either3, select3};
 
 // Embassy: timer
-use embassy_time::Timer;
+use embassy_time::{Delay, Instant, Timer};
 
 // Embassy: sync
 use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
 use embassy_sync::signal::Signal;
 
+// Async temperature sensor driver
+use tmp102_driver::{Address, Tmp102Async};
+
+// Async button debouncing and gestures
+use debounce::{ActiveLevel, ButtonAsync, Gesture, GestureRecognizer};
+
+// LED wrapper and pattern sequencer
+use led::{Led, Pattern, Sequencer};
+
 // Let panic_probe handle our panic routine
 use panic_probe as _;
 
-// Create signal handle
+// Create signal handles: blinking on/off, sensor fault present/cleared
 static SIGNAL_BLINK: Signal<CriticalSectionRawMutex, bool> = Signal::new();
+static SIGNAL_FAULT: Signal<CriticalSectionRawMutex, bool> = Signal::new();
+
+// Blink code shown while the temperature sensor fails to respond
+const FAULT_CODE: u8 = 3;
 
-// Macro to bind USB interrupt handler
+// Macro to bind USB and I2C interrupt handlers
 bind_interrupts!(struct Irqs {
     USBCTRL_IRQ => InterruptHandler<USB>;
+    I2C1_IRQ => i2c::InterruptHandler<I2C1>;
 });
 
 // Task: handle USB logging
@@ -38,38 +54,86 @@ async fn logger_task(driver: Driver<'static, USB>) {
 }
 
 // Handle button presses in main task
-async fn monitor_button(mut pin: gpio::Input<'_>, id: &str) {
+async fn monitor_button(pin: gpio::Input<'_>, id: &str) {
+    // Pressed pulls the pin low; bounces are ignored while it settles
+    let mut button = ButtonAsync::new(pin, Delay, ActiveLevel::Low);
+    let mut gestures = GestureRecognizer::default();
+    let clock = || Instant::now().as_micros();
     let mut state = false;
     loop {
-        // Yield waiting for button press
-        pin.wait_for_low().await;
-        log::info!("Button {} pressed", id);
+        // Yield waiting for a gesture (GPIO reads cannot fail)
+        let gesture = button
+            .wait_for_gesture(&mut gestures, &clock)
+            .await
+            .unwrap();
+        log::info!("Button {}: {:?}", id, gesture);
+
+        // Click toggles blinking, long press stops it
+        match gesture {
+            Gesture::Click => state = !state,
+            Gesture::LongPress => state = false,
+            _ => continue,
+        }
 
-        // Toggle and send signal to blinky thread
-        state = !state;
+        // Send signal to blinky thread
         SIGNAL_BLINK.signal(state);
-
-        // Simple debounce
-        Timer::after_millis(200).await;
-        pin.wait_for_high().await;
     }
 }
 
-// Task: blink the LED if the button is pressed
+// Task: show a heartbeat if enabled by the button, or a blink code on a
+// sensor fault
 #[embassy_executor::task]
-async fn blink_led_task(mut pin: gpio::Output<'static>) {
+async fn blink_led_task(mut led: Led<gpio::Output<'static>>) {
+    let mut sequencer = Sequencer::new();
     let mut enabled = false;
+    let mut fault = false;
+    loop {
+        // A fault takes priority over the heartbeat
+        let pattern = match (fault, enabled) {
+            (true, _) => Pattern::Code(FAULT_CODE),
+            (false, true) => Pattern::HEARTBEAT,
+            (false, false) => Pattern::Off,
+        };
+        let now_ms = Instant::now().as_millis();
+        if sequencer.pattern() != pattern {
+            sequencer.play(pattern, now_ms);
+        }
+
+        // Show the current step (GPIO writes cannot fail)
+        let next_ms = sequencer.drive(now_ms, &mut led).unwrap();
+
+        // Sleep until the next step is due or a signal arrives
+        let step = async {
+            match next_ms {
+                Some(next_ms) => Timer::at(Instant::from_millis(next_ms)).await,
+                None => core::future::pending().await,
+            }
+        };
+        match select3(step, SIGNAL_BLINK.wait(), SIGNAL_FAULT.wait()).await {
+            Either3::First(()) => {}
+            Either3::Second(state) => enabled = state,
+            Either3::Third(state) => fault = state,
+        }
+    }
+}
+
+// Task: log the temperature every second without blocking other tasks
+#[embassy_executor::task]
+async fn temperature_task(mut tmp102: Tmp102Async<i2c::I2c<'static, I2C1, i2c::Async>>) {
+    let mut fault = false;
     loop {
-        // See if signal has anything, otherwise use previous `enabled` value
-        enabled = SIGNAL_BLINK.try_take().unwrap_or(enabled);
+        let result = tmp102.read_temperature().await;
+        match result {
+            Ok(temp) => log::info!("Temperature: {:.2} deg C", temp),
+            Err(ref e) => log::error!("Error: {}", e),
+        }
 
-        // Only blink if `enabled` is `true`
-        if enabled {
-            pin.set_high();
-            Timer::after_millis(250).await;
+        // Tell the LED task when the sensor starts or stops failing
+        if result.is_err() != fault {
+            fault = result.is_err();
+            SIGNAL_FAULT.signal(fault);
         }
-        pin.set_low();
-        Timer::after_millis(250).await;
+        Timer::after_millis(1000).await;
     }
 }
 
@@ -82,11 +146,17 @@ async fn main(spawner: Spawner) {
     let usb_driver = Driver::new(p.USB, Irqs);
     let _ = spawner.spawn(logger_task(usb_driver));
 
-    // Create a new output pin
+    // Create a new output pin, lit when high
     let led_pin = gpio::Output::new(p.PIN_15, gpio::Level::Low);
+    let led = Led::active_high(led_pin);
 
     // Spawn blink task
-    spawner.spawn(blink_led_task(led_pin)).unwrap();
+    spawner.spawn(blink_led_task(led)).unwrap();
+
+    // Initialize async I2C (SDA on GPIO18, SCL on GPIO19) and sensor task
+    let i2c = i2c::I2c::new_async(p.I2C1, p.PIN_19, p.PIN_18, Irqs, i2c::Config::default());
+    let tmp102 = Tmp102Async::new(i2c, Address::Ground);
+    spawner.spawn(temperature_task(tmp102)).unwrap();
 
     // Create a new input pin with an internal pulldown
     let btn_pin = gpio::Input::new(p.PIN_14, gpio::Pull::Up);
@@ -17,18 +17,24 @@ use critical_section::Mutex;
 use rp235x_hal as hal;
 
 // Import traits for embedded abstractions
-use embedded_hal::delay::DelayNs;
 use embedded_hal::digital::{InputPin, StatefulOutputPin};
 
 // Imports for the pin definition
 use hal::gpio::{FunctionSio, Interrupt, Pin, PullUp, SioInput};
 
-// Imports for the timer interrupt
+// Imports for the GPIO and timer interrupts
 use hal::pac::interrupt;
+use hal::timer::{Alarm, Alarm0, CopyableTimer0};
+
+// Help with timing and duration
+use hal::fugit::MicrosDurationU32;
 
 // Direct access to the nested vectored interrupt controller (NVIC)
 use cortex_m::peripheral::NVIC;
 
+// Button debouncing
+use debounce::{Debouncer, Edge};
+
 // Custom panic handler: just loop forever
 #[panic_handler]
 fn panic(_info: &PanicInfo) -> ! {
@@ -43,14 +49,22 @@ pub static IMAGE_DEF: hal::block::ImageDef = hal::block::ImageDef::secure_exe();
 // Set external crystal frequency
 const XOSC_CRYSTAL_FREQ: u32 = 12_000_000;
 
+// Time the button must be stable before a press or release counts
+const DEBOUNCE_DELAY_MS: u32 = 50;
+
+// Time between samples while the button settles
+const SAMPLE_PERIOD_MS: u32 = 1;
+
 // Global state for the alarm, LED, and timer (similar to the working example)
 type ButtonPin = Pin<hal::gpio::bank0::Gpio14, FunctionSio<SioInput>, PullUp>;
 
-// Global state for the button (wrapped in Mutex for interrupt safety)
+// Global state for the button and alarm (wrapped in Mutex for interrupt safety)
 static G_BUTTON: Mutex<RefCell<Option<ButtonPin>>> = Mutex::new(RefCell::new(None));
+static G_ALARM: Mutex<RefCell<Option<Alarm0<CopyableTimer0>>>> = Mutex::new(RefCell::new(None));
 
-// Global flag for button press
+// Global flags for a button level change and a sample alarm
 static G_BTN_FLAG: AtomicBool = AtomicBool::new(false);
+static G_ALARM_FLAG: AtomicBool = AtomicBool::new(false);
 
 // Main entrypoint (custom defined for embedded targets)
 #[hal::entry]
@@ -90,45 +104,72 @@ fn main() -> ! {
     let mut led_pin = pins.gpio15.into_push_pull_output();
     let btn_pin = pins.gpio14.into_pull_up_input();
 
-    // Trigger on falling edge (button press)
+    // Trigger on falling edge (button press) and rising edge (release) so
+    // the debouncer sees both
     btn_pin.set_interrupt_enabled(Interrupt::EdgeLow, true);
+    btn_pin.set_interrupt_enabled(Interrupt::EdgeHigh, true);
 
-    // Move alarm and LED to global state for interrupt handler
+    // Alarm to wake up and sample again while the button settles
+    let mut alarm = timer.alarm_0().unwrap();
+    alarm.enable_interrupt();
+
+    // Move button and alarm to global state for interrupt handlers
     critical_section::with(|cs| {
         G_BUTTON.borrow(cs).replace(Some(btn_pin));
+        G_ALARM.borrow(cs).replace(Some(alarm));
     });
 
-    // Enable the interrupt line
+    // Enable the interrupt lines
     unsafe {
         NVIC::unmask(hal::pac::Interrupt::IO_IRQ_BANK0);
+        NVIC::unmask(hal::pac::Interrupt::TIMER0_IRQ_0);
     }
 
+    // Debounce state machine (the pin itself stays with the ISR)
+    let mut debouncer = Debouncer::new(DEBOUNCE_DELAY_MS);
+
     // Main loop
     loop {
-        // Wait for interrupt
-        cortex_m::asm::wfi();
-
-        // Check if the button was pressed (non-blocking)
-        if G_BTN_FLAG.load(Ordering::Acquire) {
-            // Clear the flag
-            G_BTN_FLAG.store(false, Ordering::Release);
+        // Wait for interrupt (with interrupts masked so a flag set just
+        // before sleeping still wakes us up)
+        cortex_m::interrupt::disable();
+        if !G_BTN_FLAG.load(Ordering::Acquire) && !G_ALARM_FLAG.load(Ordering::Acquire) {
+            cortex_m::asm::wfi();
+        }
+        unsafe {
+            cortex_m::interrupt::enable();
+        }
 
-            // Simple debounce: block for 50 ms then check button state
-            timer.delay_ms(50);
-            critical_section::with(|cs| {
+        // Sample the button after a level change or a sample alarm
+        let btn_changed = G_BTN_FLAG.swap(false, Ordering::AcqRel);
+        let alarm_fired = G_ALARM_FLAG.swap(false, Ordering::AcqRel);
+        if btn_changed || alarm_fired {
+            let pressed = critical_section::with(|cs| {
                 let mut btn_ref = G_BUTTON.borrow(cs).borrow_mut();
-                if let Some(button) = btn_ref.as_mut() {
-                    if button.is_low().unwrap_or(false) {
-                        // Toggle LED
-                        let _ = led_pin.toggle();
-                    }
-                }
+                btn_ref
+                    .as_mut()
+                    .is_some_and(|button| button.is_low().unwrap_or(false))
             });
+
+            // Toggle LED on each debounced press
+            let edge = debouncer.update(pressed, timer.get_counter().ticks());
+            if edge == Some(Edge::Pressed) {
+                let _ = led_pin.toggle();
+            }
+
+            // Sample again shortly (sleeping meanwhile) until it has settled
+            if debouncer.is_settling() {
+                critical_section::with(|cs| {
+                    if let Some(alarm) = G_ALARM.borrow(cs).borrow_mut().as_mut() {
+                        let _ = alarm.schedule(MicrosDurationU32::millis(SAMPLE_PERIOD_MS));
+                    }
+                });
+            }
         }
     }
 }
 
-// Interrupt service routine (ISR)
+// Interrupt service routine (ISR) for the button
 #[interrupt]
 fn IO_IRQ_BANK0() {
     critical_section::with(|cs| {
@@ -137,14 +178,31 @@ fn IO_IRQ_BANK0() {
 
         // Get mutable references
         if let Some(button) = btn_ref.as_mut() {
-            // Check if the interrupt source was the pin going from high to low
-            if button.interrupt_status(Interrupt::EdgeLow) {
-                // Clear the interrupt
-                button.clear_interrupt(Interrupt::EdgeLow);
-
-                // Set the flag
-                G_BTN_FLAG.store(true, Ordering::Release);
+            // Check if the interrupt source was the pin changing level
+            for edge in [Interrupt::EdgeLow, Interrupt::EdgeHigh] {
+                if button.interrupt_status(edge) {
+                    // Clear the interrupt
+                    button.clear_interrupt(edge);
+
+                    // Set the flag
+                    G_BTN_FLAG.store(true, Ordering::Release);
+                }
             }
         }
     });
 }
+
+// Interrupt service routine (ISR) for the sample alarm
+#[interrupt]
+fn TIMER0_IRQ_0() {
+    critical_section::with(|cs| {
+        // Borrow the alarm from global state
+        let mut alarm_ref = G_ALARM.borrow(cs).borrow_mut();
+
+        // Clear the interrupt and set the flag
+        if let Some(alarm) = alarm_ref.as_mut() {
+            alarm.clear_interrupt();
+            G_ALARM_FLAG.store(true, Ordering::Release);
+        }
+    });
+}
@@ -14,14 +14,17 @@ use hal::gpio::{FunctionI2C, Pin};
 use usb_device::{class_prelude::*, prelude::*};
 use usbd_serial::SerialPort;
 
-// I2C structs/functions
-use embedded_hal::{digital::InputPin, i2c::I2c};
+// Sensor driver and sensor-agnostic reporting
+use temperature_sensor::write_reading;
+use tmp102_driver::{Address, TMP102};
+
+// Button debouncing
+use debounce::{ActiveLevel, Button, Edge};
 
 // Used for the rate/frequency type
 use hal::fugit::RateExtU32;
 
 // For working with non-heap strings
-use core::fmt::Write;
 use heapless::String;
 
 // Custom panic handler: just loop forever
@@ -37,9 +40,7 @@ pub static IMAGE_DEF: hal::block::ImageDef = hal::block::ImageDef::secure_exe();
 
 // Constants
 const XOSC_CRYSTAL_FREQ: u32 = 12_000_000; // External crystal on board
-const TMP102_ADDR: u8 = 0x48; // Device address on bus
-const TMP102_REG_TEMP: u8 = 0x0; // Address of temperature register
-const DEBOUNCE_DELAY_MS: u64 = 50; // Time to wait to check pin again
+const DEBOUNCE_DELAY_MS: u32 = 50; // Time the button must be stable
 
 // Main entrypoint (custom defined for embedded targets)
 #[hal::entry]
@@ -76,14 +77,14 @@ fn main() -> ! {
     );
 
     // Configure button pin
-    let mut btn_pin = pins.gpio14.into_pull_up_input();
+    let btn_pin = pins.gpio14.into_pull_up_input();
 
     // Configure I2C pins
     let sda_pin: Pin<_, FunctionI2C, _> = pins.gpio18.reconfigure();
     let scl_pin: Pin<_, FunctionI2C, _> = pins.gpio19.reconfigure();
 
     // Initialize and take ownership of the I2C peripheral
-    let mut i2c = hal::I2C::i2c1(
+    let i2c = hal::I2C::i2c1(
         pac.I2C1,
         sda_pin,
         scl_pin,
@@ -92,6 +93,9 @@ fn main() -> ! {
         &clocks.system_clock,
     );
 
+    // Instantiate our sensor struct
+    let mut tmp102 = TMP102::new(i2c, Address::Ground);
+
     // Initialize the USB driver
     let usb_bus = UsbBusAllocator::new(hal::usb::UsbBus::new(
         pac.USB,
@@ -114,57 +118,25 @@ fn main() -> ! {
         .device_class(2) // from: https://www.usb.org/defined-class-codes
         .build();
 
-    // Read buffer
-    let mut rx_buf = [0u8; 2];
-    let mut output = String::<64>::new();
+    // String buffer for output (long enough for a driver error)
+    let mut output = String::<128>::new();
 
-    // Declare variables for debouncing
-    let mut last_debounce_time = timer.get_counter();
-    let mut btn_state = false;
+    // Debounce the button (pressed pulls the pin low), timed by the timer
+    let clock = || timer.get_counter().ticks();
+    let mut button =
+        Button::new(btn_pin, clock, ActiveLevel::Low).with_settle_ms(DEBOUNCE_DELAY_MS);
 
     // Superloop
-    let mut prev_pressed = false;
     loop {
         // Needs to be called at least every 10 ms
         let _ = usb_dev.poll(&mut [&mut serial]);
 
-        // Get button state
-        let btn_pressed = btn_pin.is_low().unwrap_or(false);
-
-        // Get timestamp if pin changed state
-        if btn_pressed != prev_pressed {
-            last_debounce_time = timer.get_counter();
+        // Only read from sensor once per (debounced) button press
+        if let Ok(Some(Edge::Pressed)) = button.poll() {
+            // Read from sensor and print the value or the error
+            output.clear();
+            write_reading(&mut tmp102, &mut output).unwrap();
+            let _ = serial.write(output.as_bytes());
         }
-
-        // Some time after the pin change event, check the button state again
-        if (timer.get_counter() - last_debounce_time).to_millis() > DEBOUNCE_DELAY_MS {
-            // If the button state has changed, save the reading
-            if btn_pressed != btn_state {
-                btn_state = btn_pressed;
-
-                // Only read from sensor if the new button state is true
-                if btn_state {
-                    // Read from sensor
-                    let result = i2c.write_read(TMP102_ADDR, &[TMP102_REG_TEMP], &mut rx_buf);
-                    if result.is_err() {
-                        let _ = serial.write(b"ERROR: Could not read temperature\r\n");
-                        continue;
-                    }
-
-                    // Convert raw reading (signed 12-bit value) into Celsius
-                    let temp_raw = ((rx_buf[0] as u16) << 8) | (rx_buf[1] as u16);
-                    let temp_signed = (temp_raw as i16) >> 4;
-                    let temp_c = (temp_signed as f32) * 0.0625;
-
-                    // Print out value
-                    output.clear();
-                    write!(&mut output, "Temperature: {:.2} deg C\r\n", temp_c).unwrap();
-                    let _ = serial.write(output.as_bytes());
-                }
-            }
-        }
-
-        // Save button pressed state for next iteration
-        prev_pressed = btn_pressed;
     }
 }
@@ -14,14 +14,17 @@ use hal::gpio::{FunctionI2C, Pin};
 use usb_device::{class_prelude::*, prelude::*};
 use usbd_serial::SerialPort;
 
-// I2C structs/functions
-use embedded_hal::{digital::InputPin, i2c::I2c};
+// GPIO structs/functions
+use embedded_hal::digital::InputPin;
+
+// Sensor driver and sensor-agnostic reporting
+use temperature_sensor::write_reading;
+use tmp102_driver::{Address, TMP102};
 
 // Used for the rate/frequency type
 use hal::fugit::RateExtU32;
 
 // For working with non-heap strings
-use core::fmt::Write;
 use heapless::String;
 
 // Custom panic handler: just loop forever
@@ -37,8 +40,6 @@ pub static IMAGE_DEF: hal::block::ImageDef = hal::block::ImageDef::secure_exe();
 
 // Constants
 const XOSC_CRYSTAL_FREQ: u32 = 12_000_000; // External crystal on board
-const TMP102_ADDR: u8 = 0x48; // Device address on bus
-const TMP102_REG_TEMP: u8 = 0x0; // Address of temperature register
 
 // Main entrypoint (custom defined for embedded targets)
 #[hal::entry]
@@ -79,7 +80,7 @@ fn main() -> ! {
     let scl_pin: Pin<_, FunctionI2C, _> = pins.gpio19.reconfigure();
 
     // Initialize and take ownership of the I2C peripheral
-    let mut i2c = hal::I2C::i2c1(
+    let i2c = hal::I2C::i2c1(
         pac.I2C1,
         sda_pin,
         scl_pin,
@@ -88,6 +89,9 @@ fn main() -> ! {
         &clocks.system_clock,
     );
 
+    // Instantiate our sensor struct
+    let mut tmp102 = TMP102::new(i2c, Address::Ground);
+
     // Initialize the USB driver
     let usb_bus = UsbBusAllocator::new(hal::usb::UsbBus::new(
         pac.USB,
@@ -110,9 +114,8 @@ fn main() -> ! {
         .device_class(2) // from: https://www.usb.org/defined-class-codes
         .build();
 
-    // Read buffer
-    let mut rx_buf = [0u8; 2];
-    let mut output = String::<64>::new();
+    // String buffer for output (long enough for a driver error)
+    let mut output = String::<128>::new();
 
     // Superloop
     let mut prev_pressed = false;
@@ -128,21 +131,9 @@ fn main() -> ! {
         let btn_pressed = btn_pin.is_low().unwrap_or(false);
 
         if btn_pressed && (!prev_pressed) {
-            // Read from sensor
-            let result = i2c.write_read(TMP102_ADDR, &[TMP102_REG_TEMP], &mut rx_buf);
-            if result.is_err() {
-                let _ = serial.write(b"ERROR: Could not read temperature\r\n");
-                continue;
-            }
-
-            // Convert raw reading (signed 12-bit value) into Celsius
-            let temp_raw = ((rx_buf[0] as u16) << 8) | (rx_buf[1] as u16);
-            let temp_signed = (temp_raw as i16) >> 4;
-            let temp_c = (temp_signed as f32) * 0.0625;
-
-            // Print out value
+            // Read from sensor and print the value or the error
             output.clear();
-            write!(&mut output, "Temperature: {:.2} deg C\r\n", temp_c).unwrap();
+            write_reading(&mut tmp102, &mut output).unwrap();
             let _ = serial.write(output.as_bytes());
         }
 
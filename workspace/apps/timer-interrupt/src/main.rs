@@ -13,19 +13,19 @@ use critical_section::Mutex;
 // Alias our HAL
 use rp235x_hal as hal;
 
-// Import traits for embedded abstractions
-use embedded_hal::digital::StatefulOutputPin;
-
 // Imports for the pin definition
 use hal::gpio::{FunctionSio, Pin, PullDown, SioOutput};
 
 // Imports for the timer interrupt
 use hal::pac::interrupt;
-use hal::timer::{Alarm, Alarm0, CopyableTimer0};
+use hal::timer::{Alarm, Alarm0, CopyableTimer0, Timer};
 
 // Direct access to the nested vectored interrupt controller (NVIC)
 use cortex_m::peripheral::NVIC;
 
+// LED wrapper and pattern sequencer
+use led::{ActiveLevel, Led, Pattern, Sequencer};
+
 // Help with timing and duration
 use fugit::MicrosDurationU32;
 
@@ -46,9 +46,13 @@ const XOSC_CRYSTAL_FREQ: u32 = 12_000_000;
 // Global state for the alarm, LED, and timer (similar to the working example)
 type LedPin = Pin<hal::gpio::bank0::Gpio15, FunctionSio<SioOutput>, PullDown>;
 
-// Global state for the alarm and LED (wrapped in Mutex for interrupt safety)
+// Global state for the alarm, timer and LED (wrapped in Mutex for interrupt safety)
 static G_ALARM: Mutex<RefCell<Option<Alarm0<CopyableTimer0>>>> = Mutex::new(RefCell::new(None));
-static G_LED: Mutex<RefCell<Option<LedPin>>> = Mutex::new(RefCell::new(None));
+static G_TIMER: Mutex<RefCell<Option<Timer<CopyableTimer0>>>> = Mutex::new(RefCell::new(None));
+static G_LED: Mutex<RefCell<Option<Led<LedPin>>>> = Mutex::new(RefCell::new(None));
+
+// Pattern played on the LED (const-constructed, so no Option needed)
+static G_SEQUENCER: Mutex<RefCell<Sequencer>> = Mutex::new(RefCell::new(Sequencer::new()));
 
 // Main entrypoint (custom defined for embedded targets)
 #[hal::entry]
@@ -84,22 +88,35 @@ fn main() -> ! {
     // Configure pin, get ownership of that pin
     let led_pin = pins.gpio15.into_push_pull_output();
 
+    // LED from GPIO15 to ground: lit when the pin is high
+    let led = Led::new(led_pin, ActiveLevel::High);
+
     // Move ownership of TIMER0 peripheral to create Timer struct
     let mut timer = hal::Timer::new_timer0(pac.TIMER0, &mut pac.RESETS, &clocks);
 
     // Create an alarm from the timer
     let mut alarm = timer.alarm_0().unwrap();
 
-    // Set the alarm to trigger in 500 ms
-    let _ = alarm.schedule(MicrosDurationU32::millis(500));
+    // Start a heartbeat; the first step is shown from the ISR right away
+    let now_ms = timer.get_counter().ticks() / 1000;
+    critical_section::with(|cs| {
+        G_SEQUENCER
+            .borrow(cs)
+            .borrow_mut()
+            .play(Pattern::HEARTBEAT, now_ms);
+    });
+
+    // Set the alarm to trigger in 1 ms
+    let _ = alarm.schedule(MicrosDurationU32::millis(1));
 
     // Enable alarm interrupt
     alarm.enable_interrupt();
 
-    // Move alarm and LED to global state for interrupt handler
+    // Move alarm, timer and LED to global state for interrupt handler
     critical_section::with(|cs| {
         G_ALARM.borrow(cs).replace(Some(alarm));
-        G_LED.borrow(cs).replace(Some(led_pin));
+        G_TIMER.borrow(cs).replace(Some(timer));
+        G_LED.borrow(cs).replace(Some(led));
     });
 
     // Enable the interrupt line
@@ -117,20 +134,28 @@ fn main() -> ! {
 #[interrupt]
 fn TIMER0_IRQ_0() {
     critical_section::with(|cs| {
-        // Borrow the alarm and LED from global state
+        // Borrow the alarm, timer, LED and sequencer from global state
         let mut alarm_ref = G_ALARM.borrow(cs).borrow_mut();
+        let timer_ref = G_TIMER.borrow(cs).borrow();
         let mut led_ref = G_LED.borrow(cs).borrow_mut();
+        let mut sequencer = G_SEQUENCER.borrow(cs).borrow_mut();
 
         // Get mutable references
-        if let (Some(alarm), Some(led)) = (alarm_ref.as_mut(), led_ref.as_mut()) {
+        if let (Some(alarm), Some(timer), Some(led)) =
+            (alarm_ref.as_mut(), timer_ref.as_ref(), led_ref.as_mut())
+        {
             // Clear the interrupt
             alarm.clear_interrupt();
 
-            // Toggle the LED
-            let _ = led.toggle();
+            // Show the current step of the pattern
+            let now_ms = timer.get_counter().ticks() / 1000;
+            let next_ms = sequencer.drive(now_ms, led);
 
-            // Schedule next interrupt in 500ms
-            let _ = alarm.schedule(fugit::MicrosDurationU32::millis(500));
+            // Schedule the next interrupt for the next step (none: hold)
+            if let Ok(Some(next_ms)) = next_ms {
+                let delay_ms = next_ms.saturating_sub(now_ms).max(1) as u32;
+                let _ = alarm.schedule(MicrosDurationU32::millis(delay_ms));
+            }
         }
     });
 }
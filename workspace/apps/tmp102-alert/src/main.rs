@@ -0,0 +1,237 @@
+#![no_std]
+#![no_main]
+
+// We need to write our own panic handler
+use core::panic::PanicInfo;
+
+// Let us modify data with only immutable reference (enforce borrow rules at runtime)
+use core::cell::RefCell;
+
+// Atomic operations for setting a global flag
+use core::sync::atomic::{AtomicBool, Ordering};
+
+// Embedded mutex (no threads): access to data by one piece of code at a time
+use critical_section::Mutex;
+
+// Alias our HAL
+use rp235x_hal as hal;
+
+// Import traits for embedded abstractions
+use embedded_hal::digital::OutputPin;
+
+// Imports for the pin definitions
+use hal::gpio::{FunctionI2C, FunctionSio, Interrupt, Pin, PullUp, SioInput};
+
+// Imports for the GPIO interrupt
+use hal::pac::interrupt;
+
+// Direct access to the nested vectored interrupt controller (NVIC)
+use cortex_m::peripheral::NVIC;
+
+// Used for the rate/frequency type
+use hal::fugit::RateExtU32;
+
+// Bring in our driver
+use tmp102_driver::{Address, Alert, AlertState, Edge, TMP102, Temperature, ThermostatMode};
+
+// Custom panic handler: just loop forever
+#[panic_handler]
+fn panic(_info: &PanicInfo) -> ! {
+    loop {}
+}
+
+// Copy boot metadata to .start_block so Boot ROM knows how to boot our program
+#[unsafe(link_section = ".start_block")]
+#[used]
+pub static IMAGE_DEF: hal::block::ImageDef = hal::block::ImageDef::secure_exe();
+
+// Constants
+const XOSC_CRYSTAL_FREQ: u32 = 12_000_000; // External crystal on board
+const T_LOW: Temperature = Temperature::from_celsius(26); // Alert clears below this
+const T_HIGH: Temperature = Temperature::from_celsius(28); // Alert asserts at this
+
+// TMP102 ALERT output (open drain, so it needs a pull-up)
+type AlertPin = Pin<hal::gpio::bank0::Gpio13, FunctionSio<SioInput>, PullUp>;
+
+// Global state for the alert pin (wrapped in Mutex for interrupt safety)
+static G_ALERT: Mutex<RefCell<Option<Alert<AlertPin>>>> = Mutex::new(RefCell::new(None));
+
+// Global flag for an alert event
+static G_ALERT_FLAG: AtomicBool = AtomicBool::new(false);
+
+// Main entrypoint (custom defined for embedded targets)
+#[hal::entry]
+fn main() -> ! {
+    // Get ownership of hardware peripherals
+    let mut pac = hal::pac::Peripherals::take().unwrap();
+
+    // Set up the watchdog and clocks
+    let mut watchdog = hal::Watchdog::new(pac.WATCHDOG);
+    let clocks = hal::clocks::init_clocks_and_plls(
+        XOSC_CRYSTAL_FREQ,
+        pac.XOSC,
+        pac.CLOCKS,
+        pac.PLL_SYS,
+        pac.PLL_USB,
+        &mut pac.RESETS,
+        &mut watchdog,
+    )
+    .ok()
+    .unwrap();
+
+    // Single-cycle I/O block (fast GPIO)
+    let sio = hal::Sio::new(pac.SIO);
+
+    // Split off ownership of Peripherals struct, set pins to default state
+    let pins = hal::gpio::Pins::new(
+        pac.IO_BANK0,
+        pac.PADS_BANK0,
+        sio.gpio_bank0,
+        &mut pac.RESETS,
+    );
+
+    // Configure LED and ALERT pins
+    let mut led_pin = pins.gpio15.into_push_pull_output();
+    let alert_pin = pins.gpio13.into_pull_up_input();
+
+    // Configure I2C pins
+    let sda_pin: Pin<_, FunctionI2C, _> = pins.gpio18.reconfigure();
+    let scl_pin: Pin<_, FunctionI2C, _> = pins.gpio19.reconfigure();
+
+    // Initialize and take ownership of the I2C peripheral
+    let i2c = hal::I2C::i2c1(
+        pac.I2C1,
+        sda_pin,
+        scl_pin,
+        100.kHz(),
+        &mut pac.RESETS,
+        &clocks.system_clock,
+    );
+
+    // Instantiate our sensor struct
+    let mut tmp102 = TMP102::new(i2c, Address::Ground);
+
+    // Interrupt mode: one event when crossing T_HIGH, then one below T_LOW.
+    // Read the configuration before switching: in interrupt mode any
+    // register read clears a pending alert.
+    tmp102.set_low_threshold(T_LOW).unwrap();
+    tmp102.set_high_threshold(T_HIGH).unwrap();
+    let config = tmp102
+        .read_config()
+        .unwrap()
+        .with_thermostat_mode(ThermostatMode::Interrupt);
+    tmp102.write_config(config).unwrap();
+
+    // Let the alert companion interpret the pin with the sensor's settings
+    let alert = Alert::new(alert_pin, config);
+
+    // Trigger on the edge where ALERT asserts (falling for active-low)
+    let edge = match alert.assert_edge() {
+        Edge::Falling => Interrupt::EdgeLow,
+        Edge::Rising => Interrupt::EdgeHigh,
+    };
+
+    // Move the alert pin to global state for interrupt handler
+    critical_section::with(|cs| {
+        let mut alert_ref = G_ALERT.borrow(cs).borrow_mut();
+        let alert = alert_ref.insert(alert);
+        alert.pin_mut().set_interrupt_enabled(edge, true);
+    });
+
+    // Enable the interrupt line
+    unsafe {
+        NVIC::unmask(hal::pac::Interrupt::IO_IRQ_BANK0);
+    }
+
+    // ALERT may already be asserted (e.g. booting above T_HIGH). In
+    // interrupt mode it then stays asserted until read, so no edge would
+    // ever come: handle it as if the interrupt had fired.
+    let asserted = critical_section::with(|cs| {
+        let mut alert_ref = G_ALERT.borrow(cs).borrow_mut();
+        alert_ref
+            .as_mut()
+            .is_some_and(|alert| alert.is_active().unwrap_or(false))
+    });
+    if asserted {
+        G_ALERT_FLAG.store(true, Ordering::Release);
+    }
+
+    // Main loop
+    loop {
+        // Wait for interrupt, unless an alert is already waiting. WFI wakes
+        // on a pending interrupt even while they are disabled, and the ISR
+        // runs once they are enabled again, so no alert slips in between
+        // the check and the sleep.
+        cortex_m::interrupt::disable();
+        if !G_ALERT_FLAG.load(Ordering::Acquire) {
+            cortex_m::asm::wfi();
+        }
+        unsafe {
+            cortex_m::interrupt::enable();
+        }
+
+        // Check if the sensor raised an alert (non-blocking)
+        if G_ALERT_FLAG.load(Ordering::Acquire) {
+            // Clear the flag
+            G_ALERT_FLAG.store(false, Ordering::Release);
+
+            // Take the alert pin out of global state for the I2C transfers,
+            // with its interrupt masked rather than all interrupts disabled
+            NVIC::mask(hal::pac::Interrupt::IO_IRQ_BANK0);
+            let alert = critical_section::with(|cs| G_ALERT.borrow(cs).take());
+
+            if let Some(mut alert) = alert {
+                // Release the ALERT line so the next event can assert it,
+                // and ask the sensor which event it was. LED on while too
+                // hot, off once cooled down.
+                match alert.acknowledge(&mut tmp102) {
+                    Ok(AlertState::OverTemp) => {
+                        let _ = led_pin.set_high();
+                    }
+                    Ok(AlertState::UnderTemp) => {
+                        let _ = led_pin.set_low();
+                    }
+                    _ => {}
+                }
+
+                // Hand the pin back to the interrupt handler
+                critical_section::with(|cs| {
+                    G_ALERT.borrow(cs).replace(Some(alert));
+                });
+            }
+
+            // An edge that came in meanwhile is still pending and fires now
+            unsafe {
+                NVIC::unmask(hal::pac::Interrupt::IO_IRQ_BANK0);
+            }
+        }
+    }
+}
+
+// Interrupt service routine (ISR)
+#[interrupt]
+fn IO_IRQ_BANK0() {
+    critical_section::with(|cs| {
+        // Borrow the alert pin from global state
+        let mut alert_ref = G_ALERT.borrow(cs).borrow_mut();
+
+        // Get mutable references
+        if let Some(alert) = alert_ref.as_mut() {
+            // Check which edge the alert asserts on
+            let edge = match alert.assert_edge() {
+                Edge::Falling => Interrupt::EdgeLow,
+                Edge::Rising => Interrupt::EdgeHigh,
+            };
+
+            // Check if the interrupt source was the ALERT pin asserting
+            let pin = alert.pin_mut();
+            if pin.interrupt_status(edge) {
+                // Clear the interrupt
+                pin.clear_interrupt(edge);
+
+                // Set the flag
+                G_ALERT_FLAG.store(true, Ordering::Release);
+            }
+        }
+    });
+}
@@ -14,18 +14,21 @@ use hal::gpio::{FunctionI2C, Pin};
 use usb_device::{class_prelude::*, prelude::*};
 use usbd_serial::SerialPort;
 
-// I2C structs/functions
-use embedded_hal::digital::InputPin;
-
 // Used for the rate/frequency type
 use hal::fugit::RateExtU32;
 
 // For working with non-heap strings
-use core::fmt::Write;
+use core::fmt::{self, Write};
 use heapless::String;
 
 // Bring in our driver
-use tmp102_driver::{Address, TMP102};
+use tmp102_driver::{Address, TMP102, Temperature};
+
+// Sensor-agnostic reading, reporting and rolling statistics
+use temperature_sensor::{RollingStats, TemperatureSensor, write_result};
+
+// Debounced button gestures
+use debounce::{ActiveLevel, Button, Gesture, GestureRecognizer};
 
 // Custom panic handler: just loop forever
 #[panic_handler]
@@ -40,6 +43,76 @@ pub static IMAGE_DEF: hal::block::ImageDef = hal::block::ImageDef::secure_exe();
 
 // Constants
 const XOSC_CRYSTAL_FREQ: u32 = 12_000_000; // External crystal on board
+const SAMPLE_PERIOD_MS: u64 = 1_000; // Time between readings
+const WINDOW_SIZE: usize = 60; // Readings kept for statistics (1 minute)
+
+// Units for printed temperatures
+#[derive(Clone, Copy)]
+enum Units {
+    Celsius,
+    Fahrenheit,
+}
+
+impl Units {
+    // The other unit
+    fn toggle(self) -> Self {
+        match self {
+            Units::Celsius => Units::Fahrenheit,
+            Units::Fahrenheit => Units::Celsius,
+        }
+    }
+
+    // Temperature in these units, for printing
+    fn format(self, temp: Temperature) -> InUnits {
+        InUnits(temp, self)
+    }
+
+    // Label for printing
+    fn label(self) -> &'static str {
+        match self {
+            Units::Celsius => "deg C",
+            Units::Fahrenheit => "deg F",
+        }
+    }
+}
+
+// Temperature printed in some units with two decimals, without floating
+// point (soft-float maths and float formatting are large on the MCU)
+struct InUnits(Temperature, Units);
+
+impl fmt::Display for InUnits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.1 {
+            Units::Celsius => write!(f, "{:.2}", self.0),
+            Units::Fahrenheit => {
+                // Round milli-degrees to hundredths, half away from zero
+                let millis = self.0.as_millifahrenheit();
+                let hundredths = (millis.unsigned_abs() + 5) / 10;
+                let sign = if millis < 0 && hundredths != 0 {
+                    "-"
+                } else {
+                    ""
+                };
+                write!(f, "{}{}.{:02}", sign, hundredths / 100, hundredths % 100)
+            }
+        }
+    }
+}
+
+// Take a reading through the sensor trait and add it to the statistics
+fn sample<S, const N: usize>(
+    sensor: &mut S,
+    stats: &mut RollingStats<N>,
+) -> Result<Temperature, S::Error>
+where
+    S: TemperatureSensor,
+{
+    let result = sensor.read_temperature();
+    if let Ok(temp) = result {
+        stats.push(temp);
+    }
+    result
+}
 
 // Main entrypoint (custom defined for embedded targets)
 #[hal::entry]
@@ -72,8 +145,11 @@ fn main() -> ! {
         &mut pac.RESETS,
     );
 
+    // Move ownership of TIMER0 peripheral to create Timer struct
+    let timer = hal::Timer::new_timer0(pac.TIMER0, &mut pac.RESETS, &clocks);
+
     // Configure button pin
-    let mut btn_pin = pins.gpio14.into_pull_up_input();
+    let btn_pin = pins.gpio14.into_pull_up_input();
 
     // Configure I2C pins
     let sda_pin: Pin<_, FunctionI2C, _> = pins.gpio18.reconfigure();
@@ -89,7 +165,8 @@ fn main() -> ! {
         &clocks.system_clock,
     );
 
-    // Instantiate our sensor struct
+    // Instantiate our sensor struct (continuous mode: a reading is one short
+    // I2C transfer, so sampling does not hold up USB servicing)
     let mut tmp102 = TMP102::new(i2c, Address::Ground);
 
     // Initialize the USB driver
@@ -114,36 +191,73 @@ fn main() -> ! {
         .device_class(2) // from: https://www.usb.org/defined-class-codes
         .build();
 
-    // String buffer for output
-    let mut output = String::<64>::new();
+    // String buffer for output (long enough for a report or driver error)
+    let mut output = String::<128>::new();
+
+    // Last minute of readings, and the latest reading or error
+    let mut stats = RollingStats::<WINDOW_SIZE>::new();
+    let mut latest = None;
+
+    // Debounced button (pressed pulls the pin low) with gesture detection
+    let clock = || timer.get_counter().ticks();
+    let mut button = Button::new(btn_pin, clock, ActiveLevel::Low);
+    let mut gestures = GestureRecognizer::default();
+    let mut units = Units::Celsius;
 
     // Superloop
-    let mut prev_pressed = false;
+    let mut timestamp = timer.get_counter();
     loop {
         // Needs to be called at least every 10 ms
         let _ = usb_dev.poll(&mut [&mut serial]);
 
-        // Wait for button press
-        let btn_pressed = btn_pin.is_low().unwrap_or(false);
-        if btn_pressed && (!prev_pressed) {
-            // Read from sensor
-            let temp_c = match tmp102.read_temperature_c() {
-                Ok(temp) => temp,
-                Err(e) => {
-                    output.clear();
-                    write!(&mut output, "Error: {:?}\r\n", e).unwrap();
-                    let _ = serial.write(output.as_bytes());
-                    continue;
-                }
-            };
-
-            // Print out value
-            output.clear();
-            write!(&mut output, "Temperature: {:.2} deg C\r\n", temp_c).unwrap();
-            let _ = serial.write(output.as_bytes());
+        // Take a reading once per sample period, reporting errors right away
+        if (timer.get_counter() - timestamp).to_millis() >= SAMPLE_PERIOD_MS {
+            timestamp = timer.get_counter();
+            let result = sample(&mut tmp102, &mut stats);
+            if result.is_err() {
+                output.clear();
+                write_result(&result, &mut output).unwrap();
+                let _ = serial.write(output.as_bytes());
+            }
+            latest = Some(result);
         }
 
-        // Save button pressed state for next iteration
-        prev_pressed = btn_pressed;
+        // Click: print the latest reading, then 1-min avg / min / max
+        // Long press: switch between Celsius and Fahrenheit
+        match button.poll_gesture(&mut gestures) {
+            Ok(Some(Gesture::Click)) => {
+                output.clear();
+                match &latest {
+                    Some(Ok(temp)) => write!(
+                        &mut output,
+                        "Temperature: {} {}\r\n",
+                        units.format(*temp),
+                        units.label()
+                    )
+                    .unwrap(),
+                    Some(result @ Err(_)) => write_result(result, &mut output).unwrap(),
+                    None => write!(&mut output, "No readings yet\r\n").unwrap(),
+                }
+                if let Some(summary) = stats.stats() {
+                    write!(
+                        &mut output,
+                        "1-min avg: {} / min: {} / max: {} {}\r\n",
+                        units.format(summary.mean),
+                        units.format(summary.min),
+                        units.format(summary.max),
+                        units.label()
+                    )
+                    .unwrap();
+                }
+                let _ = serial.write(output.as_bytes());
+            }
+            Ok(Some(Gesture::LongPress)) => {
+                units = units.toggle();
+                output.clear();
+                write!(&mut output, "Units: {}\r\n", units.label()).unwrap();
+                let _ = serial.write(output.as_bytes());
+            }
+            _ => {}
+        }
     }
 }
@@ -0,0 +1,175 @@
+#![no_std]
+#![no_main]
+
+// We need to write our own panic handler
+use core::panic::PanicInfo;
+
+// Alias our HAL
+use rp235x_hal as hal;
+
+// Bring GPIO structs/functions into scope
+use hal::gpio::{FunctionI2C, Pin};
+
+// Used for the rate/frequency type
+use hal::fugit::RateExtU32;
+
+// Bring in our driver
+use tmp102_driver::{Address, TMP102, Temperature};
+
+// PWM LEDs, colors and fades
+use led::pattern::FULL;
+use led::{Fade, Lerp, Pattern, PwmLed, Rgb, RgbLed, Sequencer};
+
+// Custom panic handler: just loop forever
+#[panic_handler]
+fn panic(_info: &PanicInfo) -> ! {
+    loop {}
+}
+
+// Copy boot metadata to .start_block so Boot ROM knows how to boot our program
+#[unsafe(link_section = ".start_block")]
+#[used]
+pub static IMAGE_DEF: hal::block::ImageDef = hal::block::ImageDef::secure_exe();
+
+// Constants
+const XOSC_CRYSTAL_FREQ: u32 = 12_000_000; // External crystal on board
+const SAMPLE_PERIOD_MS: u64 = 1_000; // Time between readings
+const FADE_MS: u32 = 800; // Time to fade to a new reading
+const COLD_MILLICELSIUS: i32 = 15_000; // Shown as blue and dim
+const HOT_MILLICELSIUS: i32 = 35_000; // Shown as red and bright
+const FAULT_CODE: u8 = 3; // Blinks on the white LED if the sensor fails
+
+// Colors from cold to hot
+const SCALE: &[Rgb] = &[Rgb::BLUE, Rgb::GREEN, Rgb::RED];
+
+// Position of a temperature between cold and hot, as (num, den)
+fn scale_position(temp: Temperature) -> (u32, u32) {
+    let span = HOT_MILLICELSIUS - COLD_MILLICELSIUS;
+    let offset = (temp.as_millicelsius() - COLD_MILLICELSIUS).clamp(0, span);
+    (offset as u32, span as u32)
+}
+
+// Main entrypoint (custom defined for embedded targets)
+#[hal::entry]
+fn main() -> ! {
+    // Get ownership of hardware peripherals
+    let mut pac = hal::pac::Peripherals::take().unwrap();
+
+    // Set up the watchdog and clocks
+    let mut watchdog = hal::Watchdog::new(pac.WATCHDOG);
+    let clocks = hal::clocks::init_clocks_and_plls(
+        XOSC_CRYSTAL_FREQ,
+        pac.XOSC,
+        pac.CLOCKS,
+        pac.PLL_SYS,
+        pac.PLL_USB,
+        &mut pac.RESETS,
+        &mut watchdog,
+    )
+    .ok()
+    .unwrap();
+
+    // Single-cycle I/O block (fast GPIO)
+    let sio = hal::Sio::new(pac.SIO);
+
+    // Split off ownership of Peripherals struct, set pins to default state
+    let pins = hal::gpio::Pins::new(
+        pac.IO_BANK0,
+        pac.PADS_BANK0,
+        sio.gpio_bank0,
+        &mut pac.RESETS,
+    );
+
+    // Move ownership of TIMER0 peripheral to create Timer struct
+    let timer = hal::Timer::new_timer0(pac.TIMER0, &mut pac.RESETS, &clocks);
+
+    // Split the PWM block into its slices (each drives two pins, A and B)
+    let pwm_slices = hal::pwm::Slices::new(pac.PWM, &mut pac.RESETS);
+
+    // Full 16-bit range: 150 MHz / 65536 is about 2.3 kHz, too fast to see
+    let mut pwm5 = pwm_slices.pwm5;
+    pwm5.set_top(u16::MAX);
+    pwm5.enable();
+    let mut pwm6 = pwm_slices.pwm6;
+    pwm6.set_top(u16::MAX);
+    pwm6.enable();
+    let mut pwm7 = pwm_slices.pwm7;
+    pwm7.set_top(u16::MAX);
+    pwm7.enable();
+
+    // RGB LED (common cathode): red on GPIO10 (5A), green on GPIO11 (5B),
+    // blue on GPIO12 (6A)
+    pwm5.channel_a.output_to(pins.gpio10);
+    pwm5.channel_b.output_to(pins.gpio11);
+    pwm6.channel_a.output_to(pins.gpio12);
+    let mut rgb = RgbLed::new(
+        &mut pwm5.channel_a,
+        &mut pwm5.channel_b,
+        &mut pwm6.channel_a,
+    );
+
+    // Single LED from GPIO15 (7B) to ground, gamma-corrected brightness
+    pwm7.channel_b.output_to(pins.gpio15);
+    let mut led = PwmLed::new(&mut pwm7.channel_b).with_gamma();
+
+    // Configure I2C pins
+    let sda_pin: Pin<_, FunctionI2C, _> = pins.gpio18.reconfigure();
+    let scl_pin: Pin<_, FunctionI2C, _> = pins.gpio19.reconfigure();
+
+    // Initialize and take ownership of the I2C peripheral
+    let i2c = hal::I2C::i2c1(
+        pac.I2C1,
+        sda_pin,
+        scl_pin,
+        100.kHz(),
+        &mut pac.RESETS,
+        &clocks.system_clock,
+    );
+
+    // Instantiate our sensor struct
+    let mut tmp102 = TMP102::new(i2c, Address::Ground);
+
+    // Fades toward the latest reading (start dark)
+    let mut color = Fade::new(Rgb::OFF, Rgb::OFF, 0, 0);
+    let mut brightness = Fade::new(0, 0, 0, 0);
+
+    // Blink code shown instead of the brightness while the sensor fails
+    let mut sequencer = Sequencer::new();
+    let mut fault = false;
+
+    // Superloop
+    let mut next_sample_ms = 0;
+    loop {
+        let now_ms = timer.get_counter().ticks() / 1000;
+
+        // Read the sensor once per sample period and fade to the new values
+        if now_ms >= next_sample_ms {
+            next_sample_ms = now_ms + SAMPLE_PERIOD_MS;
+            match tmp102.read_temperature() {
+                Ok(temp) => {
+                    let (num, den) = scale_position(temp);
+                    let target = Rgb::gradient(SCALE, num, den);
+                    color = Fade::new(color.value(now_ms), target, now_ms, FADE_MS);
+                    let target = 0.lerp(FULL, num, den);
+                    brightness = Fade::new(brightness.value(now_ms), target, now_ms, FADE_MS);
+                    fault = false;
+                }
+                Err(_) => {
+                    if !fault {
+                        sequencer.play(Pattern::Code(FAULT_CODE), now_ms);
+                    }
+                    color = Fade::new(color.value(now_ms), Rgb::OFF, now_ms, FADE_MS);
+                    fault = true;
+                }
+            }
+        }
+
+        // Update the LEDs (PWM writes cannot fail)
+        let _ = rgb.set_color(color.value(now_ms));
+        if fault {
+            let _ = sequencer.drive(now_ms, &mut led);
+        } else {
+            let _ = brightness.drive(now_ms, &mut led);
+        }
+    }
+}
@@ -21,11 +21,11 @@ use embedded_hal::digital::InputPin;
 use hal::fugit::RateExtU32;
 
 // For working with non-heap strings
-use core::fmt::Write;
 use heapless::String;
 
-// Bring in our driver
-use tmp1x2::{SlaveAddr, Tmp1x2};
+// Sensor driver and sensor-agnostic reporting
+use temperature_sensor::write_reading;
+use tmp102_driver::{Address, TMP102};
 
 // Custom panic handler: just loop forever
 #[panic_handler]
@@ -89,8 +89,8 @@ fn main() -> ! {
         &clocks.system_clock,
     );
 
-    // Instantiate tmp1x2
-    let mut tmp1x2 = Tmp1x2::new(i2c, SlaveAddr::Default);
+    // Instantiate our sensor struct
+    let mut tmp102 = TMP102::new(i2c, Address::Ground);
 
     // Initialize the USB driver
     let usb_bus = UsbBusAllocator::new(hal::usb::UsbBus::new(
@@ -114,8 +114,8 @@ fn main() -> ! {
         .device_class(2) // from: https://www.usb.org/defined-class-codes
         .build();
 
-    // String buffer for output
-    let mut output = String::<64>::new();
+    // String buffer for output (long enough for a driver error)
+    let mut output = String::<128>::new();
 
     // Superloop
     let mut prev_pressed = false;
@@ -126,20 +126,9 @@ fn main() -> ! {
         // Wait for button press
         let btn_pressed = btn_pin.is_low().unwrap_or(false);
         if btn_pressed && (!prev_pressed) {
-            // Read from sensor
-            let temp_c = match tmp1x2.read_temperature() {
-                Ok(temp) => temp,
-                Err(e) => {
-                    output.clear();
-                    write!(&mut output, "Error: {:?}\r\n", e).unwrap();
-                    let _ = serial.write(output.as_bytes());
-                    continue;
-                }
-            };
-
-            // Print out value
+            // Read from sensor and print the value or the error
             output.clear();
-            write!(&mut output, "Temperature: {:.2} deg C\r\n", temp_c).unwrap();
+            write_reading(&mut tmp102, &mut output).unwrap();
             let _ = serial.write(output.as_bytes());
         }
 
@@ -0,0 +1,296 @@
+//! Async button built on `embedded-hal-async`
+//!
+//! Instead of polling, [`ButtonAsync`] sleeps on the pin until it changes
+//! level, waits out the settle time, and checks that the new level held.
+//! With Embassy, pass a `gpio::Input` and `embassy_time::Delay`:
+//!
+//! ```ignore
+//! let mut button = ButtonAsync::new(btn_pin, Delay, ActiveLevel::Low);
+//! loop {
+//!     button.wait_for_press().await.unwrap();
+//!     // ...
+//! }
+//! ```
+//!
+//! For gestures, also pass a clock, e.g. `|| Instant::now().as_micros()`,
+//! to [`ButtonAsync::wait_for_gesture`].
+
+use embassy_futures::select::{Either, select};
+use embedded_hal::digital::InputPin;
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::digital::Wait;
+
+use crate::{ActiveLevel, Clock, DEFAULT_SETTLE_MS, Edge, Gesture, GestureRecognizer};
+
+/// Debounced button on an async input pin
+pub struct ButtonAsync<P, D> {
+    pin: P,
+    delay: D,
+    active: ActiveLevel,
+    settle_ms: u32,
+    pressed: bool,
+}
+
+impl<P, D> ButtonAsync<P, D>
+where
+    P: InputPin + Wait,
+    D: DelayNs,
+{
+    /// Wrap an input pin, using [`DEFAULT_SETTLE_MS`]
+    ///
+    /// The button starts released.
+    pub fn new(pin: P, delay: D, active: ActiveLevel) -> Self {
+        Self {
+            pin,
+            delay,
+            active,
+            settle_ms: DEFAULT_SETTLE_MS,
+            pressed: false,
+        }
+    }
+
+    /// Use a different settle time
+    pub fn with_settle_ms(mut self, settle_ms: u32) -> Self {
+        self.settle_ms = settle_ms;
+        self
+    }
+
+    /// Wait for the next change of the debounced state
+    ///
+    /// A change that does not survive the settle time is ignored. Dropping
+    /// the future early (e.g. on a timeout) loses no edges.
+    pub async fn wait_for_edge(&mut self) -> Result<Edge, P::Error> {
+        let target = !self.pressed;
+        loop {
+            // Sleep until the pin reaches the other level
+            match (self.active, target) {
+                (ActiveLevel::Low, true) | (ActiveLevel::High, false) => {
+                    self.pin.wait_for_low().await?
+                }
+                (ActiveLevel::Low, false) | (ActiveLevel::High, true) => {
+                    self.pin.wait_for_high().await?
+                }
+            }
+
+            // Let it settle, then check it is still there
+            self.delay.delay_ms(self.settle_ms).await;
+            if self.active.is_pressed(&mut self.pin)? == target {
+                self.pressed = target;
+                return Ok(if target {
+                    Edge::Pressed
+                } else {
+                    Edge::Released
+                });
+            }
+        }
+    }
+
+    /// Wait for the next press
+    pub async fn wait_for_press(&mut self) -> Result<(), P::Error> {
+        while self.wait_for_edge().await? != Edge::Pressed {}
+        Ok(())
+    }
+
+    /// Wait for the next release
+    pub async fn wait_for_release(&mut self) -> Result<(), P::Error> {
+        while self.wait_for_edge().await? != Edge::Released {}
+        Ok(())
+    }
+
+    /// Debounced state as of the last edge
+    pub fn is_pressed(&self) -> bool {
+        self.pressed
+    }
+
+    /// Wait for the next gesture
+    ///
+    /// Sleeps on the pin, waking early only when the recognizer has a
+    /// click, long press or repeat due. The delay is cloned for that
+    /// timeout, which is free for `embassy_time::Delay`.
+    pub async fn wait_for_gesture<C: Clock>(
+        &mut self,
+        recognizer: &mut GestureRecognizer,
+        clock: &C,
+    ) -> Result<Gesture, P::Error>
+    where
+        D: Clone,
+    {
+        loop {
+            let edge = match recognizer.deadline_us() {
+                Some(deadline_us) => {
+                    // Wait for an edge or the deadline, whichever comes first
+                    let wait_us = deadline_us.saturating_sub(clock.now_us());
+                    let mut timeout = self.delay.clone();
+                    let timeout = timeout.delay_us(wait_us.min(u32::MAX as u64) as u32);
+                    match select(self.wait_for_edge(), timeout).await {
+                        Either::First(edge) => Some(edge?),
+                        Either::Second(()) => None,
+                    }
+                }
+                None => Some(self.wait_for_edge().await?),
+            };
+            if let Some(gesture) = recognizer.update(edge, clock.now_us()) {
+                return Ok(gesture);
+            }
+        }
+    }
+
+    /// Release the pin and delay
+    pub fn release(self) -> (P, D) {
+        (self.pin, self.delay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    // Import top-level structs/functions
+    use super::*;
+
+    // Test-only imports
+    use crate::tests::{BOUNCY_PRESS, ScriptedPin, VirtualTime};
+    use core::future::pending;
+
+    impl ScriptedPin<'_> {
+        // Sleep until the pin is at `high` (forever if the script never
+        // gets there)
+        async fn wait_for_level(&self, high: bool) {
+            if self.level() == high {
+                return;
+            }
+            match self.next_change(high) {
+                Some(at_us) => self.time.sleep_until(at_us).await,
+                None => pending().await,
+            }
+        }
+    }
+
+    // Pins replay their script as the virtual clock moves
+    impl Wait for ScriptedPin<'_> {
+        async fn wait_for_high(&mut self) -> Result<(), Self::Error> {
+            self.wait_for_level(true).await;
+            Ok(())
+        }
+
+        async fn wait_for_low(&mut self) -> Result<(), Self::Error> {
+            self.wait_for_level(false).await;
+            Ok(())
+        }
+
+        async fn wait_for_rising_edge(&mut self) -> Result<(), Self::Error> {
+            self.wait_for_level(false).await;
+            self.wait_for_level(true).await;
+            Ok(())
+        }
+
+        async fn wait_for_falling_edge(&mut self) -> Result<(), Self::Error> {
+            self.wait_for_level(true).await;
+            self.wait_for_level(false).await;
+            Ok(())
+        }
+
+        async fn wait_for_any_edge(&mut self) -> Result<(), Self::Error> {
+            let level = self.level();
+            self.wait_for_level(!level).await;
+            Ok(())
+        }
+    }
+
+    // Delay on the virtual clock
+    #[derive(Clone)]
+    struct VirtualDelay<'a>(&'a VirtualTime);
+
+    impl DelayNs for VirtualDelay<'_> {
+        async fn delay_ns(&mut self, ns: u32) {
+            let at_us = self.0.now_us() + ns.div_ceil(1000) as u64;
+            self.0.sleep_until(at_us).await;
+        }
+    }
+
+    // Edges are reported a settle time after the pin first changes
+    #[test]
+    fn test_bouncy_press() {
+        let time = VirtualTime::new();
+        let pin = ScriptedPin {
+            time: &time,
+            script: BOUNCY_PRESS,
+        };
+        let mut button = ButtonAsync::new(pin, VirtualDelay(&time), ActiveLevel::Low);
+        assert_eq!(
+            time.block_on(button.wait_for_edge()).unwrap(),
+            Edge::Pressed
+        );
+        assert_eq!(time.now_us(), 30_000);
+        assert!(button.is_pressed());
+        assert_eq!(
+            time.block_on(button.wait_for_edge()).unwrap(),
+            Edge::Released
+        );
+        assert_eq!(time.now_us(), 120_000);
+    }
+
+    // Changes that revert within the settle time are ignored
+    #[test]
+    fn test_glitch() {
+        // 5 ms glitch at 10 ms, then a real press at 50 ms
+        let script = [(10, false), (15, true), (50, false), (90, true)];
+        let time = VirtualTime::new();
+        let pin = ScriptedPin {
+            time: &time,
+            script: &script,
+        };
+        let mut button = ButtonAsync::new(pin, VirtualDelay(&time), ActiveLevel::Low);
+        time.block_on(button.wait_for_press()).unwrap();
+        assert_eq!(time.now_us(), 70_000);
+        time.block_on(button.wait_for_release()).unwrap();
+        assert_eq!(time.now_us(), 110_000);
+    }
+
+    // Active-high wiring with a longer settle time
+    #[test]
+    fn test_active_high() {
+        let script = [(0, false), (5, true), (100, false)];
+        let time = VirtualTime::new();
+        let pin = ScriptedPin {
+            time: &time,
+            script: &script,
+        };
+        let mut button =
+            ButtonAsync::new(pin, VirtualDelay(&time), ActiveLevel::High).with_settle_ms(50);
+        time.block_on(button.wait_for_press()).unwrap();
+        assert_eq!(time.now_us(), 55_000);
+        let (_pin, _delay) = button.release();
+    }
+
+    // Gestures with timeouts racing the pin
+    #[test]
+    fn test_wait_for_gesture() {
+        // Double click, then a single click, then a hold from 2000 to 3000 ms
+        let script = [
+            (100, false),
+            (150, true),
+            (300, false),
+            (350, true),
+            (1000, false),
+            (1050, true),
+            (2000, false),
+            (3000, true),
+        ];
+        let time = VirtualTime::new();
+        let pin = ScriptedPin {
+            time: &time,
+            script: &script,
+        };
+        let mut button = ButtonAsync::new(pin, VirtualDelay(&time), ActiveLevel::Low);
+        let mut recognizer = GestureRecognizer::default();
+        let clock = || time.now_us();
+        let mut next = || {
+            let gesture = time.block_on(button.wait_for_gesture(&mut recognizer, &clock));
+            (time.now_us() / 1000, gesture.unwrap())
+        };
+        assert_eq!(next(), (320, Gesture::DoubleClick));
+        assert_eq!(next(), (1370, Gesture::Click));
+        assert_eq!(next(), (2620, Gesture::LongPress));
+        assert_eq!(next(), (2820, Gesture::Repeat));
+    }
+}
@@ -0,0 +1,206 @@
+//! Polled button on a GPIO input
+//!
+//! [`Button`] reads the pin and the clock itself on every
+//! [`Button::poll`], so a superloop only has to call it often enough
+//! (well under the settle time) and act on the edges it returns.
+
+use embedded_hal::digital::InputPin;
+
+use crate::{Clock, DEFAULT_SETTLE_MS, Debouncer, Edge, Gesture, GestureRecognizer};
+
+/// Pin level when the button is pressed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActiveLevel {
+    /// Pressed pulls the pin low (button to ground with a pull-up)
+    Low,
+    /// Pressed pulls the pin high (button to 3.3 V with a pull-down)
+    High,
+}
+
+impl ActiveLevel {
+    /// Read whether the button is pressed, without debouncing
+    pub(crate) fn is_pressed<P: InputPin>(self, pin: &mut P) -> Result<bool, P::Error> {
+        match self {
+            ActiveLevel::Low => pin.is_low(),
+            ActiveLevel::High => pin.is_high(),
+        }
+    }
+}
+
+/// Debounced button on an input pin
+pub struct Button<P, C> {
+    pin: P,
+    clock: C,
+    active: ActiveLevel,
+    debouncer: Debouncer,
+}
+
+impl<P, C> Button<P, C>
+where
+    P: InputPin,
+    C: Clock,
+{
+    /// Wrap an input pin, using [`DEFAULT_SETTLE_MS`]
+    ///
+    /// The button starts released. If it is held at startup, a
+    /// [`Edge::Pressed`] is reported once it has settled.
+    pub fn new(pin: P, clock: C, active: ActiveLevel) -> Self {
+        Self {
+            pin,
+            clock,
+            active,
+            debouncer: Debouncer::new(DEFAULT_SETTLE_MS),
+        }
+    }
+
+    /// Use a different settle time
+    pub fn with_settle_ms(mut self, settle_ms: u32) -> Self {
+        self.debouncer = Debouncer::new(settle_ms);
+        self
+    }
+
+    /// Sample the pin, returning an edge if the debounced state changed
+    pub fn poll(&mut self) -> Result<Option<Edge>, P::Error> {
+        let pressed = self.active.is_pressed(&mut self.pin)?;
+        Ok(self.debouncer.update(pressed, self.clock.now_us()))
+    }
+
+    /// Sample the pin and pass the result to a gesture recognizer
+    ///
+    /// Use instead of [`Button::poll`], not alongside it, so the recognizer
+    /// sees every edge.
+    pub fn poll_gesture(
+        &mut self,
+        recognizer: &mut GestureRecognizer,
+    ) -> Result<Option<Gesture>, P::Error> {
+        let now_us = self.clock.now_us();
+        let pressed = self.active.is_pressed(&mut self.pin)?;
+        let edge = self.debouncer.update(pressed, now_us);
+        Ok(recognizer.update(edge, now_us))
+    }
+
+    /// Debounced state as of the last poll
+    pub fn is_pressed(&self) -> bool {
+        self.debouncer.is_pressed()
+    }
+
+    /// Access the pin, e.g. to clear its interrupt in a handler
+    pub fn pin_mut(&mut self) -> &mut P {
+        &mut self.pin
+    }
+
+    /// Release the pin and clock
+    pub fn release(self) -> (P, C) {
+        (self.pin, self.clock)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    // Import top-level structs/functions
+    use super::*;
+
+    // Explicitly link to std
+    extern crate std;
+    use std::vec::Vec;
+
+    // Test-only imports
+    use crate::tests::{BOUNCY_PRESS, ScriptedPin, VirtualTime};
+
+    // Poll once per millisecond up to `end_ms`, returning edges with times
+    fn run<P: InputPin, C: Clock>(
+        button: &mut Button<P, C>,
+        time: &VirtualTime,
+        end_ms: u64,
+    ) -> Vec<(u64, Edge)> {
+        let mut edges = Vec::new();
+        for time_ms in 0..=end_ms {
+            time.set_ms(time_ms);
+            if let Some(edge) = button.poll().unwrap() {
+                edges.push((time_ms, edge));
+            }
+        }
+        edges
+    }
+
+    // One edge per press and release, a settle time after the last bounce
+    #[test]
+    fn test_bouncy_press() {
+        let time = VirtualTime::new();
+        let pin = ScriptedPin {
+            time: &time,
+            script: BOUNCY_PRESS,
+        };
+        let mut button = Button::new(pin, || time.now_us(), ActiveLevel::Low);
+        let edges = run(&mut button, &time, 200);
+        assert_eq!(edges, [(35, Edge::Pressed), (125, Edge::Released)]);
+        assert!(!button.is_pressed());
+    }
+
+    // Longer settle time and active-high wiring
+    #[test]
+    fn test_settle_and_level() {
+        // Pressed (high) from 10 to 60 ms with a 1 ms glitch at 40 ms
+        let script = [(0, false), (10, true), (40, false), (41, true), (60, false)];
+        let time = VirtualTime::new();
+        let pin = ScriptedPin {
+            time: &time,
+            script: &script,
+        };
+        let mut button = Button::new(pin, || time.now_us(), ActiveLevel::High).with_settle_ms(15);
+        let edges = run(&mut button, &time, 100);
+        assert_eq!(edges, [(25, Edge::Pressed), (75, Edge::Released)]);
+    }
+
+    // Taps shorter than the settle time are not presses
+    #[test]
+    fn test_short_tap() {
+        let script = [(10, false), (12, true), (50, false), (65, true)];
+        let time = VirtualTime::new();
+        let pin = ScriptedPin {
+            time: &time,
+            script: &script,
+        };
+        let mut button = Button::new(pin, || time.now_us(), ActiveLevel::Low);
+        assert!(run(&mut button, &time, 100).is_empty());
+    }
+
+    // Gestures from a bouncy button polled in a superloop
+    #[test]
+    fn test_poll_gesture() {
+        // Bouncy click, then a hold from 1000 to 1900 ms
+        let script = [
+            (10, false),
+            (12, true),
+            (13, false),
+            (80, true),
+            (1000, false),
+            (1900, true),
+        ];
+        let time = VirtualTime::new();
+        let pin = ScriptedPin {
+            time: &time,
+            script: &script,
+        };
+        let mut button = Button::new(pin, || time.now_us(), ActiveLevel::Low);
+        let mut recognizer = GestureRecognizer::default();
+        let mut gestures = Vec::new();
+        for time_ms in 0..=2500 {
+            time.set_ms(time_ms);
+            if let Some(gesture) = button.poll_gesture(&mut recognizer).unwrap() {
+                gestures.push((time_ms, gesture));
+            }
+        }
+
+        // Release settles at 100 ms, long press 600 ms after 1020 ms
+        assert_eq!(
+            gestures,
+            [
+                (400, Gesture::Click),
+                (1620, Gesture::LongPress),
+                (1820, Gesture::Repeat)
+            ]
+        );
+    }
+}
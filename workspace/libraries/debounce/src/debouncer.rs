@@ -0,0 +1,158 @@
+//! Debounce state machine
+//!
+//! Holds the accepted (debounced) state and, while the raw input disagrees
+//! with it, the time the disagreement started. Once the input has
+//! disagreed for the whole settle time, the new state is accepted.
+
+/// Change of the debounced button state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edge {
+    /// The button was pressed
+    Pressed,
+    /// The button was released
+    Released,
+}
+
+/// Time-based debounce state machine
+///
+/// Feed it raw samples with [`Debouncer::update`]. The input must be
+/// sampled at least once after the settle time has passed for an edge to
+/// be reported, so poll it faster than the settle time.
+#[derive(Debug, Clone)]
+pub struct Debouncer {
+    settle_us: u64,
+    pressed: bool,
+    changed_at: Option<u64>,
+}
+
+impl Debouncer {
+    /// Create a debouncer that starts released
+    pub const fn new(settle_ms: u32) -> Self {
+        Self {
+            settle_us: settle_ms as u64 * 1000,
+            pressed: false,
+            changed_at: None,
+        }
+    }
+
+    /// Add a raw sample taken at `now_us`, returning an edge if the
+    /// debounced state changed
+    ///
+    /// A clock that went backwards delays the edge rather than firing it early.
+    pub fn update(&mut self, pressed: bool, now_us: u64) -> Option<Edge> {
+        if pressed == self.pressed {
+            self.changed_at = None;
+            return None;
+        }
+
+        // Wait until the new level has held for the settle time
+        let since = *self.changed_at.get_or_insert(now_us);
+        if now_us.saturating_sub(since) < self.settle_us {
+            return None;
+        }
+
+        self.pressed = pressed;
+        self.changed_at = None;
+        Some(if pressed {
+            Edge::Pressed
+        } else {
+            Edge::Released
+        })
+    }
+
+    /// Debounced state
+    pub fn is_pressed(&self) -> bool {
+        self.pressed
+    }
+
+    /// Whether the raw input currently disagrees with the debounced state
+    ///
+    /// Keep sampling while this is true; an edge may be about to fire.
+    pub fn is_settling(&self) -> bool {
+        self.changed_at.is_some()
+    }
+
+    /// Force the debounced state, e.g. to the level read at startup
+    pub fn reset(&mut self, pressed: bool) {
+        self.pressed = pressed;
+        self.changed_at = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    // Import top-level structs/functions
+    use super::*;
+
+    // Explicitly link to std
+    extern crate std;
+    use std::vec::Vec;
+
+    // Feed `(time_ms, pressed)` samples, returning edges with their times
+    fn run(debouncer: &mut Debouncer, samples: &[(u64, bool)]) -> Vec<(u64, Edge)> {
+        samples
+            .iter()
+            .filter_map(|&(time_ms, pressed)| {
+                let edge = debouncer.update(pressed, time_ms * 1000)?;
+                Some((time_ms, edge))
+            })
+            .collect()
+    }
+
+    // Level must hold for the settle time before an edge fires
+    #[test]
+    fn test_settle() {
+        let mut debouncer = Debouncer::new(20);
+        assert!(!debouncer.is_pressed());
+        let samples = [(0, true), (10, true), (19, true), (20, true), (30, true)];
+        assert_eq!(run(&mut debouncer, &samples), [(20, Edge::Pressed)]);
+        assert!(debouncer.is_pressed());
+        assert!(!debouncer.is_settling());
+
+        let samples = [(40, false), (59, false), (60, false)];
+        assert_eq!(run(&mut debouncer, &samples), [(60, Edge::Released)]);
+    }
+
+    // Any bounce back restarts the settle time
+    #[test]
+    fn test_bounce_restarts() {
+        let mut debouncer = Debouncer::new(20);
+        let samples = [(0, true), (15, false), (16, true), (35, true), (36, true)];
+        assert_eq!(run(&mut debouncer, &samples), [(36, Edge::Pressed)]);
+    }
+
+    // Glitches shorter than the settle time are ignored
+    #[test]
+    fn test_glitch() {
+        let mut debouncer = Debouncer::new(20);
+        let samples = [(0, true), (5, true), (10, false), (100, false)];
+        assert!(run(&mut debouncer, &samples).is_empty());
+        assert!(!debouncer.is_pressed());
+    }
+
+    // Zero settle time follows the input; reset forces the state
+    #[test]
+    fn test_zero_and_reset() {
+        let mut debouncer = Debouncer::new(0);
+        assert_eq!(debouncer.update(true, 0), Some(Edge::Pressed));
+        assert_eq!(debouncer.update(false, 0), Some(Edge::Released));
+
+        let mut debouncer = Debouncer::new(20);
+        debouncer.update(true, 0);
+        assert!(debouncer.is_settling());
+        debouncer.reset(true);
+        assert!(debouncer.is_pressed());
+        assert!(!debouncer.is_settling());
+        assert_eq!(debouncer.update(true, 100_000), None);
+    }
+
+    // Clock going backwards delays the edge instead of firing it early
+    #[test]
+    fn test_clock_backwards() {
+        let mut debouncer = Debouncer::new(20);
+        debouncer.update(true, 50_000);
+        assert_eq!(debouncer.update(true, 10_000), None);
+        assert_eq!(debouncer.update(true, 70_000), Some(Edge::Pressed));
+    }
+}
@@ -0,0 +1,324 @@
+//! Gesture recognition on debounced edges
+//!
+//! [`GestureRecognizer`] turns the press and release [`Edge`]s of one
+//! button into clicks, double clicks, long presses and auto-repeat. It
+//! only sees edges and timestamps, so the same timings apply whether the
+//! edges come from a polled [`crate::Button`] or an async button, and host
+//! tests can drive it with exact times.
+//!
+//! Timeline of each gesture (with the default [`Timings`]):
+//!
+//! - [`Gesture::Click`]: press and release, then no second press for
+//!   300 ms (reported when that window ends)
+//! - [`Gesture::DoubleClick`]: a second press within 300 ms of the first
+//!   release (reported on the second press; the rest of that press is
+//!   ignored)
+//! - [`Gesture::LongPress`]: held for 600 ms (reported while still held)
+//! - [`Gesture::Repeat`]: every 200 ms while held after a long press
+
+use crate::Edge;
+
+/// Recognized button gesture
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gesture {
+    /// Short press with no second press following it
+    Click,
+    /// Two short presses in quick succession
+    DoubleClick,
+    /// Held past the long press time
+    LongPress,
+    /// Still held, one per repeat interval after a long press
+    Repeat,
+}
+
+/// Gesture timings in milliseconds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timings {
+    /// Hold time for a long press
+    pub long_press_ms: u32,
+    /// Longest gap between a release and the next press of a double click
+    /// (0 disables double clicks, so clicks are reported on release)
+    pub double_click_ms: u32,
+    /// Interval between repeats while held (0 disables repeat)
+    pub repeat_ms: u32,
+}
+
+impl Timings {
+    /// Default timings
+    pub const DEFAULT: Self = Self {
+        long_press_ms: 600,
+        double_click_ms: 300,
+        repeat_ms: 200,
+    };
+}
+
+impl Default for Timings {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Recognizer state between edges
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// Released, nothing pending
+    Idle,
+    /// Held; `next_us` is when the next long press or repeat is due
+    Held { next_us: u64, long: bool },
+    /// Released after a click, waiting to see if a second press follows
+    WaitSecond { deadline_us: u64 },
+    /// Held after a double click, until released
+    Ignored,
+}
+
+/// Gesture state machine for one button
+#[derive(Debug, Clone)]
+pub struct GestureRecognizer {
+    timings: Timings,
+    state: State,
+}
+
+impl GestureRecognizer {
+    /// Create a recognizer with the given timings
+    pub const fn new(timings: Timings) -> Self {
+        Self {
+            timings,
+            state: State::Idle,
+        }
+    }
+
+    /// Add the result of one button poll: an edge, or `None` if the
+    /// debounced state did not change
+    ///
+    /// Call this regularly even without edges, since clicks, long presses
+    /// and repeats are reported when their time comes. A late call reports
+    /// the overdue gesture first, so nothing is lost when polling is slow.
+    pub fn update(&mut self, edge: Option<Edge>, now_us: u64) -> Option<Gesture> {
+        match (self.state, edge) {
+            (State::Idle, Some(Edge::Pressed)) => {
+                self.press(now_us);
+                None
+            }
+            (State::Held { next_us, long }, Some(Edge::Released)) => {
+                self.state = State::Idle;
+                if long {
+                    None
+                } else if now_us >= next_us {
+                    Some(Gesture::LongPress)
+                } else if self.timings.double_click_ms == 0 {
+                    Some(Gesture::Click)
+                } else {
+                    self.state = State::WaitSecond {
+                        deadline_us: now_us + ms_to_us(self.timings.double_click_ms),
+                    };
+                    None
+                }
+            }
+            (State::Held { next_us, long }, None) if now_us >= next_us => {
+                let next_us = match self.timings.repeat_ms {
+                    0 => u64::MAX,
+                    repeat_ms => next_us + ms_to_us(repeat_ms),
+                };
+                self.state = State::Held {
+                    next_us,
+                    long: true,
+                };
+                Some(if long {
+                    Gesture::Repeat
+                } else {
+                    Gesture::LongPress
+                })
+            }
+            (State::WaitSecond { deadline_us }, Some(Edge::Pressed)) => {
+                if now_us < deadline_us {
+                    self.state = State::Ignored;
+                    Some(Gesture::DoubleClick)
+                } else {
+                    // The window had already closed: report the first
+                    // click and start tracking this press
+                    self.press(now_us);
+                    Some(Gesture::Click)
+                }
+            }
+            (State::WaitSecond { deadline_us }, None) if now_us >= deadline_us => {
+                self.state = State::Idle;
+                Some(Gesture::Click)
+            }
+            (State::Ignored, Some(Edge::Released)) => {
+                self.state = State::Idle;
+                None
+            }
+            _ => None,
+        }
+    }
+
+    /// When the next gesture is due if no edge arrives first, if any
+    ///
+    /// Async code can sleep until then instead of polling.
+    pub fn deadline_us(&self) -> Option<u64> {
+        match self.state {
+            State::Held { next_us, .. } if next_us != u64::MAX => Some(next_us),
+            State::WaitSecond { deadline_us } => Some(deadline_us),
+            _ => None,
+        }
+    }
+
+    /// Forget any gesture in progress
+    pub fn reset(&mut self) {
+        self.state = State::Idle;
+    }
+
+    /// Start tracking a press
+    fn press(&mut self, now_us: u64) {
+        self.state = State::Held {
+            next_us: now_us + ms_to_us(self.timings.long_press_ms),
+            long: false,
+        };
+    }
+}
+
+impl Default for GestureRecognizer {
+    fn default() -> Self {
+        Self::new(Timings::DEFAULT)
+    }
+}
+
+/// Milliseconds to microseconds
+const fn ms_to_us(ms: u32) -> u64 {
+    ms as u64 * 1000
+}
+
+#[cfg(test)]
+mod tests {
+
+    // Import top-level structs/functions
+    use super::*;
+
+    // Explicitly link to std
+    extern crate std;
+    use std::vec::Vec;
+
+    // Shorthand for edges
+    use Edge::{Pressed, Released};
+
+    // Step through time 1 ms at a time, applying `(time_ms, edge)` events,
+    // and return gestures with the time they were reported
+    fn run(
+        recognizer: &mut GestureRecognizer,
+        edges: &[(u64, Edge)],
+        end_ms: u64,
+    ) -> Vec<(u64, Gesture)> {
+        (0..=end_ms)
+            .filter_map(|time_ms| {
+                let edge = edges
+                    .iter()
+                    .find(|(at_ms, _)| *at_ms == time_ms)
+                    .map(|(_, edge)| *edge);
+                let gesture = recognizer.update(edge, time_ms * 1000)?;
+                Some((time_ms, gesture))
+            })
+            .collect()
+    }
+
+    // Click is reported once the double click window has passed
+    #[test]
+    fn test_click() {
+        let mut recognizer = GestureRecognizer::default();
+        let gestures = run(&mut recognizer, &[(0, Pressed), (100, Released)], 1000);
+        assert_eq!(gestures, [(400, Gesture::Click)]);
+    }
+
+    // Second press within the window is a double click
+    #[test]
+    fn test_double_click() {
+        let mut recognizer = GestureRecognizer::default();
+        let edges = [
+            (0, Pressed),
+            (100, Released),
+            (350, Pressed),
+            (1500, Released),
+        ];
+        let gestures = run(&mut recognizer, &edges, 2000);
+        assert_eq!(gestures, [(350, Gesture::DoubleClick)]);
+
+        // Too slow: two separate clicks
+        let edges = [
+            (0, Pressed),
+            (100, Released),
+            (400, Pressed),
+            (450, Released),
+        ];
+        let gestures = run(&mut recognizer, &edges, 1000);
+        assert_eq!(gestures, [(400, Gesture::Click), (750, Gesture::Click)]);
+    }
+
+    // Holding reports a long press, then repeats until released
+    #[test]
+    fn test_long_press_and_repeat() {
+        let mut recognizer = GestureRecognizer::default();
+        let gestures = run(&mut recognizer, &[(0, Pressed), (1050, Released)], 2000);
+        assert_eq!(
+            gestures,
+            [
+                (600, Gesture::LongPress),
+                (800, Gesture::Repeat),
+                (1000, Gesture::Repeat)
+            ]
+        );
+        assert_eq!(recognizer.deadline_us(), None);
+    }
+
+    // Custom timings, with double click and repeat disabled
+    #[test]
+    fn test_timings() {
+        let timings = Timings {
+            long_press_ms: 1000,
+            double_click_ms: 0,
+            repeat_ms: 0,
+        };
+        let mut recognizer = GestureRecognizer::new(timings);
+        let edges = [
+            (0, Pressed),
+            (50, Released),
+            (100, Pressed),
+            (150, Released),
+            (200, Pressed),
+            (3000, Released),
+        ];
+        let gestures = run(&mut recognizer, &edges, 4000);
+        assert_eq!(
+            gestures,
+            [
+                (50, Gesture::Click),
+                (150, Gesture::Click),
+                (1200, Gesture::LongPress)
+            ]
+        );
+    }
+
+    // Late updates still report overdue gestures
+    #[test]
+    fn test_late_updates() {
+        let mut recognizer = GestureRecognizer::default();
+        recognizer.update(Some(Pressed), 0);
+        assert_eq!(recognizer.deadline_us(), Some(600_000));
+        assert_eq!(
+            recognizer.update(Some(Released), 700_000),
+            Some(Gesture::LongPress)
+        );
+
+        // Click owed from before the next press
+        recognizer.update(Some(Pressed), 1_000_000);
+        recognizer.update(Some(Released), 1_100_000);
+        assert_eq!(recognizer.deadline_us(), Some(1_400_000));
+        assert_eq!(
+            recognizer.update(Some(Pressed), 2_000_000),
+            Some(Gesture::Click)
+        );
+        assert_eq!(recognizer.update(None, 2_600_000), Some(Gesture::LongPress));
+
+        // Reset drops the press in progress
+        recognizer.reset();
+        assert_eq!(recognizer.update(None, 5_000_000), None);
+    }
+}
@@ -0,0 +1,199 @@
+#![no_std]
+
+//! # Debounce
+//!
+//! Button debouncing that can be tested on the host.
+//!
+//! A mechanical contact bounces for a few milliseconds when it opens or
+//! closes. [`Debouncer`] only accepts a new level once the input has held
+//! it for a settle time, and reports each accepted change as an [`Edge`].
+//! Any bounce back to the old level restarts the wait.
+//!
+//! - [`Debouncer`]: the state machine alone, fed with samples and
+//!   timestamps (e.g. from a main loop that shares the pin with an ISR)
+//! - [`Button`]: owns an `embedded-hal` input pin and a [`Clock`], and is
+//!   polled from a superloop
+//! - `ButtonAsync`: waits for edges on an async pin such as Embassy's
+//!   `Input` (enable with the `async` feature)
+//!
+//! On top of the edges, [`GestureRecognizer`] detects clicks, double
+//! clicks, long presses and auto-repeat, see [`Button::poll_gesture`].
+//!
+//! Time comes from a [`Clock`] (re-exported from the `monotonic` crate, the
+//! same one the sensor driver uses), such as a closure over a HAL timer:
+//!
+//! ```ignore
+//! let clock = || timer.get_counter().ticks();
+//! let mut button = Button::new(btn_pin, clock, ActiveLevel::Low);
+//! if let Ok(Some(Edge::Pressed)) = button.poll() {
+//!     // ...
+//! }
+//! ```
+
+// Debounce state machine
+mod debouncer;
+pub use debouncer::{Debouncer, Edge};
+
+// Polled button on a GPIO input
+mod button;
+pub use button::{ActiveLevel, Button};
+
+// Click, double click, long press and repeat
+mod gesture;
+pub use gesture::{Gesture, GestureRecognizer, Timings};
+
+// Async button (enable with the `async` feature)
+#[cfg(feature = "async")]
+mod asynch;
+#[cfg(feature = "async")]
+pub use asynch::ButtonAsync;
+
+/// Settle time used unless another is given, in milliseconds
+pub const DEFAULT_SETTLE_MS: u32 = 20;
+
+// Shared time source
+pub use monotonic::Clock;
+
+#[cfg(test)]
+mod tests {
+
+    // Test-only imports
+    use core::cell::Cell;
+    use core::convert::Infallible;
+    use core::future::{Future, poll_fn};
+    use core::pin::pin;
+    use core::task::{Context, Poll, Waker};
+    use embedded_hal::digital::{ErrorType, InputPin};
+
+    /// Virtual clock shared by scripted pins, delays and the code under test
+    pub struct VirtualTime {
+        now_us: Cell<u64>,
+        wake_us: Cell<u64>,
+    }
+
+    impl VirtualTime {
+        // Start at time zero
+        pub fn new() -> Self {
+            Self {
+                now_us: Cell::new(0),
+                wake_us: Cell::new(u64::MAX),
+            }
+        }
+
+        // Current time in microseconds
+        pub fn now_us(&self) -> u64 {
+            self.now_us.get()
+        }
+
+        // Jump to a time in milliseconds
+        pub fn set_ms(&self, time_ms: u64) {
+            self.now_us.set(time_ms * 1000);
+        }
+
+        // Future that completes once the clock reaches `at_us`
+        pub fn sleep_until(&self, at_us: u64) -> impl Future<Output = ()> + '_ {
+            poll_fn(move |_| {
+                if self.now_us.get() >= at_us {
+                    return Poll::Ready(());
+                }
+                self.wake_us.set(self.wake_us.get().min(at_us));
+                Poll::Pending
+            })
+        }
+
+        // Run a future to completion, jumping the clock to the earliest
+        // wake-up whenever everything it waits on is pending
+        pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+            let mut future = pin!(future);
+            let mut cx = Context::from_waker(Waker::noop());
+            loop {
+                self.wake_us.set(u64::MAX);
+                if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                    return output;
+                }
+                let wake_us = self.wake_us.get();
+                assert_ne!(wake_us, u64::MAX, "nothing left to wait for");
+                self.now_us.set(wake_us);
+            }
+        }
+    }
+
+    /// Pin that replays a script of `(time_ms, high)` level changes
+    ///
+    /// The level before the first entry is high (a released button with a
+    /// pull-up).
+    pub struct ScriptedPin<'a> {
+        pub time: &'a VirtualTime,
+        pub script: &'a [(u64, bool)],
+    }
+
+    impl ScriptedPin<'_> {
+        // Level at the current virtual time
+        pub fn level(&self) -> bool {
+            let now_ms = self.time.now_us() / 1000;
+            self.script
+                .iter()
+                .take_while(|(time_ms, _)| *time_ms <= now_ms)
+                .last()
+                .is_none_or(|(_, high)| *high)
+        }
+
+        // Time of the next change to `high` in microseconds, if any
+        pub fn next_change(&self, high: bool) -> Option<u64> {
+            let now_ms = self.time.now_us() / 1000;
+            self.script
+                .iter()
+                .find(|(time_ms, level)| *time_ms > now_ms && *level == high)
+                .map(|(time_ms, _)| time_ms * 1000)
+        }
+    }
+
+    impl ErrorType for ScriptedPin<'_> {
+        type Error = Infallible;
+    }
+
+    impl InputPin for ScriptedPin<'_> {
+        fn is_high(&mut self) -> Result<bool, Self::Error> {
+            Ok(self.level())
+        }
+
+        fn is_low(&mut self) -> Result<bool, Self::Error> {
+            Ok(!self.level())
+        }
+    }
+
+    // A press and a release, each bouncing for 5 ms (active low)
+    pub const BOUNCY_PRESS: &[(u64, bool)] = &[
+        (10, false),
+        (11, true),
+        (12, false),
+        (14, true),
+        (15, false),
+        (100, true),
+        (101, false),
+        (103, true),
+        (104, false),
+        (105, true),
+    ];
+
+    // Script replays the levels it was given
+    #[test]
+    fn test_scripted_pin() {
+        let time = VirtualTime::new();
+        let mut pin = ScriptedPin {
+            time: &time,
+            script: BOUNCY_PRESS,
+        };
+        assert!(pin.is_high().unwrap());
+        time.set_ms(12);
+        assert!(pin.is_low().unwrap());
+        assert_eq!(pin.next_change(true), Some(14_000));
+        time.set_ms(200);
+        assert!(pin.is_high().unwrap());
+        assert_eq!(pin.next_change(false), None);
+
+        // Sleeping jumps the clock forward
+        time.block_on(time.sleep_until(250_000));
+        assert_eq!(time.now_us(), 250_000);
+    }
+}
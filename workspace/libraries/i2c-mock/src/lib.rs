@@ -0,0 +1,373 @@
+//! # I2C Mock
+//!
+//! Transaction-recording I2C mock for host tests
+//!
+//! Tests declare the transactions a driver should perform, with the bytes
+//! to return or an error to inject. The mock checks every bus access against
+//! the next expectation and panics on any mismatch, so register pointers,
+//! byte order and transfer lengths are all verified:
+//!
+//! ```
+//! use embedded_hal::i2c::I2c;
+//! use i2c_mock::{I2cMock, Transaction};
+//!
+//! let mut i2c = I2cMock::new(&[
+//!     Transaction::write(0x48, &[0x01, 0x60, 0xA0]),
+//!     Transaction::write_read(0x48, &[0x00], &[0x19, 0x00]),
+//! ]);
+//!
+//! i2c.write(0x48, &[0x01, 0x60, 0xA0]).unwrap();
+//! let mut buf = [0u8; 2];
+//! i2c.write_read(0x48, &[0x00], &mut buf).unwrap();
+//! assert_eq!(buf, [0x19, 0x00]);
+//!
+//! i2c.done();
+//! ```
+//!
+//! Clones share their state, so a test can keep one handle to call
+//! [`I2cMock::done`] after moving the other into a driver.
+
+use std::sync::{Arc, Mutex};
+
+use embedded_hal::i2c::{self, ErrorKind, Operation};
+
+/// One expected operation within a transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Op {
+    /// Controller writes these bytes
+    Write(Vec<u8>),
+    /// Controller reads, and the mock responds with these bytes
+    Read(Vec<u8>),
+}
+
+/// One expected bus transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transaction {
+    address: u8,
+    operations: Vec<Op>,
+    error: Option<ErrorKind>,
+}
+
+impl Transaction {
+    /// Expect a transaction made of several operations
+    pub fn new(address: u8, operations: &[Op]) -> Self {
+        Self {
+            address,
+            operations: operations.to_vec(),
+            error: None,
+        }
+    }
+
+    /// Expect a write of `bytes`
+    pub fn write(address: u8, bytes: &[u8]) -> Self {
+        Self::new(address, &[Op::Write(bytes.to_vec())])
+    }
+
+    /// Expect a read, responding with `response`
+    pub fn read(address: u8, response: &[u8]) -> Self {
+        Self::new(address, &[Op::Read(response.to_vec())])
+    }
+
+    /// Expect a write of `bytes` followed by a read returning `response`
+    pub fn write_read(address: u8, bytes: &[u8], response: &[u8]) -> Self {
+        Self::new(
+            address,
+            &[Op::Write(bytes.to_vec()), Op::Read(response.to_vec())],
+        )
+    }
+
+    /// Fail the transaction with an error of the given kind
+    ///
+    /// The operations are still checked, but read buffers are left untouched.
+    pub fn with_error(mut self, kind: ErrorKind) -> Self {
+        self.error = Some(kind);
+        self
+    }
+
+    /// Target address
+    pub fn address(&self) -> u8 {
+        self.address
+    }
+
+    /// Operations in order (for reads, the bytes returned)
+    pub fn operations(&self) -> &[Op] {
+        &self.operations
+    }
+}
+
+/// Error returned for injected failures
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MockError(pub ErrorKind);
+
+impl i2c::Error for MockError {
+    fn kind(&self) -> ErrorKind {
+        self.0
+    }
+}
+
+/// State shared between clones
+#[derive(Debug, Default)]
+struct State {
+    expected: Vec<Transaction>,
+    next: usize,
+    recorded: Vec<Transaction>,
+}
+
+/// I2C mock that checks transactions against expectations
+#[derive(Debug, Clone, Default)]
+pub struct I2cMock {
+    state: Arc<Mutex<State>>,
+}
+
+impl I2cMock {
+    /// Create a mock expecting these transactions in order
+    pub fn new(expected: &[Transaction]) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(State {
+                expected: expected.to_vec(),
+                ..State::default()
+            })),
+        }
+    }
+
+    /// Append more expectations
+    pub fn expect(&mut self, expected: &[Transaction]) {
+        self.lock().expected.extend_from_slice(expected);
+    }
+
+    /// Every transaction performed so far, including failed ones
+    pub fn recorded(&self) -> Vec<Transaction> {
+        self.lock().recorded.clone()
+    }
+
+    /// Panic unless every expected transaction was performed
+    pub fn done(&mut self) {
+        let state = self.lock();
+        let remaining = &state.expected[state.next..];
+        assert!(
+            remaining.is_empty(),
+            "i2c-mock: {} expected transaction(s) not performed, next: {:?}",
+            remaining.len(),
+            remaining.first()
+        );
+    }
+
+    /// Lock the shared state (a panic in another test handle is not our concern)
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Check a transaction against the next expectation and fill reads
+    fn execute(&mut self, address: u8, operations: &mut [Operation<'_>]) -> Result<(), MockError> {
+        let mut state = self.lock();
+        let index = state.next;
+        let Some(expected) = state.expected.get(index).cloned() else {
+            panic!(
+                "i2c-mock: unexpected transaction #{} to 0x{:02X}: {:?}",
+                index, address, operations
+            );
+        };
+        state.next += 1;
+
+        assert_eq!(
+            address, expected.address,
+            "i2c-mock: transaction #{} address mismatch",
+            index
+        );
+        assert_eq!(
+            operations.len(),
+            expected.operations.len(),
+            "i2c-mock: transaction #{} has {:?}, expected {:?}",
+            index,
+            operations,
+            expected.operations
+        );
+
+        let mut performed = Vec::with_capacity(operations.len());
+        for (i, (actual, expected_op)) in
+            operations.iter_mut().zip(&expected.operations).enumerate()
+        {
+            match (actual, expected_op) {
+                (Operation::Write(bytes), Op::Write(expected_bytes)) => {
+                    assert_eq!(
+                        *bytes,
+                        expected_bytes.as_slice(),
+                        "i2c-mock: transaction #{} operation {} wrote wrong bytes",
+                        index,
+                        i
+                    );
+                    performed.push(Op::Write(bytes.to_vec()));
+                }
+                (Operation::Read(buf), Op::Read(response)) => {
+                    assert_eq!(
+                        buf.len(),
+                        response.len(),
+                        "i2c-mock: transaction #{} operation {} read length mismatch",
+                        index,
+                        i
+                    );
+                    if expected.error.is_none() {
+                        buf.copy_from_slice(response);
+                    }
+                    performed.push(Op::Read(buf.to_vec()));
+                }
+                (actual, expected_op) => panic!(
+                    "i2c-mock: transaction #{} operation {} is {:?}, expected {:?}",
+                    index, i, actual, expected_op
+                ),
+            }
+        }
+
+        state.recorded.push(Transaction {
+            address,
+            operations: performed,
+            error: expected.error,
+        });
+        match expected.error {
+            Some(kind) => Err(MockError(kind)),
+            None => Ok(()),
+        }
+    }
+}
+
+impl i2c::ErrorType for I2cMock {
+    type Error = MockError;
+}
+
+impl i2c::I2c for I2cMock {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.execute(address, operations)
+    }
+}
+
+impl embedded_hal_async::i2c::I2c for I2cMock {
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.execute(address, operations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    // Import top-level structs/functions
+    use super::*;
+
+    // Test-only imports
+    use embassy_futures::block_on;
+    use embedded_hal::i2c::{I2c, NoAcknowledgeSource};
+
+    // Expected transactions pass and reads are filled in
+    #[test]
+    fn test_expected_transactions() {
+        let mut i2c = I2cMock::new(&[
+            Transaction::write(0x48, &[0x01, 0x60, 0xA0]),
+            Transaction::read(0x48, &[0x12]),
+            Transaction::write_read(0x48, &[0x00], &[0x19, 0x00]),
+        ]);
+
+        i2c.write(0x48, &[0x01, 0x60, 0xA0]).unwrap();
+        let mut buf = [0u8; 1];
+        i2c.read(0x48, &mut buf).unwrap();
+        assert_eq!(buf, [0x12]);
+        let mut buf = [0u8; 2];
+        i2c.write_read(0x48, &[0x00], &mut buf).unwrap();
+        assert_eq!(buf, [0x19, 0x00]);
+
+        i2c.done();
+        assert_eq!(i2c.recorded().len(), 3);
+        assert_eq!(
+            i2c.recorded()[2].operations()[1],
+            Op::Read(vec![0x19, 0x00])
+        );
+    }
+
+    // Injected errors are returned and still recorded
+    #[test]
+    fn test_injected_error() {
+        let nack = ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address);
+        let mut i2c =
+            I2cMock::new(&[Transaction::write_read(0x49, &[0x00], &[0xAA, 0xBB]).with_error(nack)]);
+
+        let mut buf = [0u8; 2];
+        let result = i2c.write_read(0x49, &[0x00], &mut buf);
+        assert_eq!(result, Err(MockError(nack)));
+        assert_eq!(buf, [0x00, 0x00]);
+        i2c.done();
+        assert_eq!(i2c.recorded()[0].address(), 0x49);
+    }
+
+    // Clones share expectations and the log
+    #[test]
+    fn test_clones_share_state() {
+        let mut i2c = I2cMock::new(&[Transaction::write(0x48, &[0x00])]);
+        let mut handle = i2c.clone();
+        handle.write(0x48, &[0x00]).unwrap();
+        i2c.expect(&[Transaction::write(0x48, &[0x02])]);
+        handle.write(0x48, &[0x02]).unwrap();
+        i2c.done();
+    }
+
+    // The async trait is checked the same way
+    #[test]
+    fn test_async() {
+        let mut i2c = I2cMock::new(&[Transaction::write_read(0x48, &[0x00], &[0x7F, 0xF0])]);
+        let mut buf = [0u8; 2];
+        block_on(embedded_hal_async::i2c::I2c::write_read(
+            &mut i2c,
+            0x48,
+            &[0x00],
+            &mut buf,
+        ))
+        .unwrap();
+        assert_eq!(buf, [0x7F, 0xF0]);
+        i2c.done();
+    }
+
+    // Wrong bytes fail the test
+    #[test]
+    #[should_panic(expected = "wrote wrong bytes")]
+    fn test_wrong_bytes() {
+        let mut i2c = I2cMock::new(&[Transaction::write(0x48, &[0x01, 0x60, 0xA0])]);
+        let _ = i2c.write(0x48, &[0x01, 0xA0, 0x60]);
+    }
+
+    // Wrong address fails the test
+    #[test]
+    #[should_panic(expected = "address mismatch")]
+    fn test_wrong_address() {
+        let mut i2c = I2cMock::new(&[Transaction::write(0x48, &[0x00])]);
+        let _ = i2c.write(0x49, &[0x00]);
+    }
+
+    // Wrong operation kind fails the test
+    #[test]
+    #[should_panic(expected = "expected")]
+    fn test_wrong_operation() {
+        let mut i2c = I2cMock::new(&[Transaction::write_read(0x48, &[0x00], &[0, 0])]);
+        let _ = i2c.write(0x48, &[0x00]);
+    }
+
+    // Extra transactions fail the test
+    #[test]
+    #[should_panic(expected = "unexpected transaction")]
+    fn test_unexpected_transaction() {
+        let mut i2c = I2cMock::new(&[]);
+        let _ = i2c.write(0x48, &[0x00]);
+    }
+
+    // Missing transactions fail the test
+    #[test]
+    #[should_panic(expected = "not performed")]
+    fn test_missing_transaction() {
+        let mut i2c = I2cMock::new(&[Transaction::write(0x48, &[0x00])]);
+        i2c.done();
+    }
+}
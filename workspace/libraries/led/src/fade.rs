@@ -0,0 +1,185 @@
+//! Smooth transitions between brightness levels or colors
+//!
+//! A [`Fade`] moves from one value to another over a fixed time. Like the
+//! [`Sequencer`](crate::Sequencer) it never blocks: ask it for the value at
+//! the current time as often as you like.
+//!
+//! ```ignore
+//! let mut fade = Fade::new(0, FULL, now_ms, 1000);
+//! while let Some(next_ms) = fade.drive(now_ms, &mut led)? {
+//!     // sleep until `next_ms`
+//! }
+//! // To change course half way, start a new fade from where it is
+//! fade = Fade::new(fade.value(now_ms), 0, now_ms, 500);
+//! ```
+
+use crate::Brightness;
+
+/// Fades: time between brightness changes
+const FADE_STEP_MS: u32 = 10;
+
+/// Values that can be interpolated
+pub trait Lerp: Copy {
+    /// Value `num / den` of the way from `self` to `to` (rounded to the
+    /// nearest step; `num` past `den` gives `to`)
+    fn lerp(self, to: Self, num: u32, den: u32) -> Self;
+}
+
+impl Lerp for u8 {
+    fn lerp(self, to: Self, num: u32, den: u32) -> Self {
+        if num >= den {
+            return to;
+        }
+
+        // Round the absolute value half up, so a fade down retraces the
+        // same levels as a fade up
+        let (from, to, num, den) = (self as i64, to as i64, num as i64, den as i64);
+        ((from * den + (to - from) * num + den / 2) / den) as u8
+    }
+}
+
+/// Linear transition between two values over a fixed time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fade<T = u8> {
+    from: T,
+    to: T,
+    start_ms: u64,
+    duration_ms: u32,
+}
+
+impl<T> Fade<T>
+where
+    T: Lerp,
+{
+    /// Fade from `from` to `to`, starting at `now_ms`
+    pub const fn new(from: T, to: T, now_ms: u64, duration_ms: u32) -> Self {
+        Self {
+            from,
+            to,
+            start_ms: now_ms,
+            duration_ms,
+        }
+    }
+
+    /// Value at `now_ms` (`from` before the start, `to` after the end)
+    pub fn value(&self, now_ms: u64) -> T {
+        let elapsed_ms = now_ms.saturating_sub(self.start_ms);
+        if elapsed_ms >= self.duration_ms as u64 {
+            return self.to;
+        }
+        self.from.lerp(self.to, elapsed_ms as u32, self.duration_ms)
+    }
+
+    /// Value the fade ends on
+    pub fn target(&self) -> T {
+        self.to
+    }
+
+    /// When the fade ends
+    pub fn end_ms(&self) -> u64 {
+        self.start_ms + self.duration_ms as u64
+    }
+
+    /// Whether the fade has reached its target by `now_ms`
+    pub fn is_done(&self, now_ms: u64) -> bool {
+        now_ms >= self.end_ms()
+    }
+
+    /// When to update next, or `None` once the fade is done
+    pub fn deadline_ms(&self, now_ms: u64) -> Option<u64> {
+        if self.is_done(now_ms) {
+            None
+        } else {
+            Some((now_ms + FADE_STEP_MS as u64).min(self.end_ms()))
+        }
+    }
+}
+
+impl Fade<u8> {
+    /// Set the LED to the brightness at `now_ms` and return the next
+    /// deadline
+    pub fn drive<L: Brightness>(&self, now_ms: u64, led: &mut L) -> Result<Option<u64>, L::Error> {
+        led.set_brightness(self.value(now_ms))?;
+        Ok(self.deadline_ms(now_ms))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    // Import top-level structs/functions
+    use super::*;
+
+    // Test-only imports
+    use crate::pattern::FULL;
+    use core::convert::Infallible;
+
+    // Interpolation hits both ends and rounds to the nearest step
+    #[test]
+    fn test_lerp() {
+        assert_eq!(0.lerp(FULL, 0, 10), 0);
+        assert_eq!(0.lerp(FULL, 10, 10), FULL);
+        assert_eq!(0.lerp(FULL, 20, 10), FULL);
+        assert_eq!(0.lerp(FULL, 1, 2), 128);
+        assert_eq!(FULL.lerp(0, 1, 2), 128);
+        assert_eq!(10.lerp(20, 1, 4), 13);
+        assert_eq!(20.lerp(10, 1, 4), 18);
+        assert_eq!(42.lerp(42, 3, 7), 42);
+
+        // Zero length: straight to the target
+        assert_eq!(0.lerp(FULL, 0, 0), FULL);
+
+        // Large spans do not overflow
+        assert_eq!(0.lerp(FULL, u32::MAX - 1, u32::MAX), FULL);
+    }
+
+    // Fading up and down are mirror images
+    #[test]
+    fn test_lerp_symmetric() {
+        for num in 0..=100 {
+            let up = 0.lerp(FULL, num, 100);
+            let down = FULL.lerp(0, 100 - num, 100);
+            assert_eq!(up, down, "step {}", num);
+        }
+    }
+
+    // A fade holds its start before it begins and its target after it ends
+    #[test]
+    fn test_fade_value() {
+        let fade = Fade::new(200, 100, 1000, 400);
+        assert_eq!(fade.value(0), 200);
+        assert_eq!(fade.value(1000), 200);
+        assert_eq!(fade.value(1100), 175);
+        assert_eq!(fade.value(1300), 125);
+        assert_eq!(fade.value(1400), 100);
+        assert_eq!(fade.value(u64::MAX), 100);
+        assert_eq!(fade.target(), 100);
+        assert!(!fade.is_done(1399));
+        assert!(fade.is_done(1400));
+    }
+
+    // Driving a fade steps the LED until it reaches the target
+    #[test]
+    fn test_fade_drive() {
+        struct Levels(u8, u32);
+        impl Brightness for Levels {
+            type Error = Infallible;
+            fn set_brightness(&mut self, level: u8) -> Result<(), Self::Error> {
+                assert!(level >= self.0, "fade up went down");
+                self.0 = level;
+                self.1 += 1;
+                Ok(())
+            }
+        }
+
+        let fade = Fade::new(0, FULL, 0, 95);
+        let mut led = Levels(0, 0);
+        let mut now_ms = 0;
+        while let Some(next_ms) = fade.drive(now_ms, &mut led).unwrap() {
+            now_ms = next_ms;
+        }
+        assert_eq!(now_ms, 95);
+        assert_eq!(led.0, FULL);
+        assert_eq!(led.1, 11);
+    }
+}
@@ -0,0 +1,303 @@
+#![no_std]
+
+//! # LED
+//!
+//! A generic LED on any `embedded-hal` output pin, so applications say
+//! "on" and "off" instead of "high" and "low".
+//!
+//! [`Led`] hides the wiring: an LED from the pin to ground is lit by a high
+//! level ([`ActiveLevel::High`]), one from 3.3 V to the pin by a low level
+//! ([`ActiveLevel::Low`]). Pin errors are returned rather than unwrapped;
+//! with the RP2350 HAL they are `Infallible`.
+//!
+//! ```ignore
+//! let mut led = Led::new(pins.gpio15.into_push_pull_output(), ActiveLevel::High);
+//! led.on()?;
+//! led.toggle()?;
+//! assert!(!led.is_on()?);
+//! ```
+//!
+//! [`Sequencer`] plays blink patterns, error codes, breathing and Morse
+//! code without blocking, on a [`Led`] or a dimmable [`PwmLed`].
+//!
+//! [`PwmLed`] and [`RgbLed`] set brightness and color through PWM, with
+//! [`gamma`] correction so levels look evenly spaced, and [`Fade`] moves
+//! smoothly between brightness levels or [`Rgb`] colors.
+
+use embedded_hal::digital::{OutputPin, StatefulOutputPin};
+
+// Dimmable LED on a PWM channel
+mod pwm;
+pub use pwm::{PwmLed, gamma};
+
+// Fades between levels and colors
+mod fade;
+pub use fade::{Fade, Lerp};
+
+// RGB colors and LEDs
+mod rgb;
+pub use rgb::{Rgb, RgbLed};
+
+// Non-blocking patterns
+mod morse;
+pub mod pattern;
+pub use pattern::{Pattern, Sequencer};
+
+/// Anything that can show an LED brightness
+pub trait Brightness {
+    /// Error returned by the output
+    type Error;
+
+    /// Show a brightness from 0 (off) to 255 (full)
+    fn set_brightness(&mut self, level: u8) -> Result<(), Self::Error>;
+}
+
+/// Pin level that lights the LED
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActiveLevel {
+    /// Lit by a high level (LED from the pin to ground)
+    High,
+    /// Lit by a low level (LED from 3.3 V to the pin)
+    Low,
+}
+
+/// LED on an output pin
+pub struct Led<P> {
+    pin: P,
+    active: ActiveLevel,
+}
+
+impl<P> Led<P>
+where
+    P: OutputPin,
+{
+    /// Wrap an output pin
+    ///
+    /// The pin is left as it is; call [`Led::off`] to start from a known
+    /// state.
+    pub fn new(pin: P, active: ActiveLevel) -> Self {
+        Self { pin, active }
+    }
+
+    /// Wrap a pin that lights the LED when high
+    pub fn active_high(pin: P) -> Self {
+        Self::new(pin, ActiveLevel::High)
+    }
+
+    /// Wrap a pin that lights the LED when low
+    pub fn active_low(pin: P) -> Self {
+        Self::new(pin, ActiveLevel::Low)
+    }
+
+    /// Turn the LED on
+    pub fn on(&mut self) -> Result<(), P::Error> {
+        self.set(true)
+    }
+
+    /// Turn the LED off
+    pub fn off(&mut self) -> Result<(), P::Error> {
+        self.set(false)
+    }
+
+    /// Turn the LED on or off
+    pub fn set(&mut self, on: bool) -> Result<(), P::Error> {
+        if on == (self.active == ActiveLevel::High) {
+            self.pin.set_high()
+        } else {
+            self.pin.set_low()
+        }
+    }
+
+    /// Pin level that lights the LED
+    pub fn active_level(&self) -> ActiveLevel {
+        self.active
+    }
+
+    /// Release the pin
+    pub fn release(self) -> P {
+        self.pin
+    }
+}
+
+impl<P> Led<P>
+where
+    P: StatefulOutputPin,
+{
+    /// Switch the LED to the opposite state
+    pub fn toggle(&mut self) -> Result<(), P::Error> {
+        self.pin.toggle()
+    }
+
+    /// Whether the LED is on (from the pin's output register)
+    pub fn is_on(&mut self) -> Result<bool, P::Error> {
+        match self.active {
+            ActiveLevel::High => self.pin.is_set_high(),
+            ActiveLevel::Low => self.pin.is_set_low(),
+        }
+    }
+}
+
+impl<P> Brightness for Led<P>
+where
+    P: OutputPin,
+{
+    type Error = P::Error;
+
+    /// On from half brightness (128) up, off below
+    fn set_brightness(&mut self, level: u8) -> Result<(), Self::Error> {
+        self.set(level >= 128)
+    }
+}
+
+impl<P> From<P> for Led<P>
+where
+    P: OutputPin,
+{
+    /// Wrap a pin that lights the LED when high
+    fn from(pin: P) -> Self {
+        Self::active_high(pin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    // Import top-level structs/functions
+    use super::*;
+
+    // Test-only imports
+    use core::convert::Infallible;
+    use embedded_hal::digital::{Error, ErrorKind, ErrorType, PinState};
+    use embedded_hal::pwm::{self, SetDutyCycle};
+
+    // Error reported by the pin stub
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct PinError;
+
+    impl Error for PinError {
+        fn kind(&self) -> ErrorKind {
+            ErrorKind::Other
+        }
+    }
+
+    // Output pin stub that remembers its level and can be told to fail
+    #[derive(Debug, Default)]
+    pub struct PinStub {
+        pub high: bool,
+        pub writes: u32,
+        pub fail: bool,
+    }
+
+    impl ErrorType for PinStub {
+        type Error = PinError;
+    }
+
+    impl OutputPin for PinStub {
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.set_state(PinState::Low)
+        }
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.set_state(PinState::High)
+        }
+
+        fn set_state(&mut self, state: PinState) -> Result<(), Self::Error> {
+            if self.fail {
+                return Err(PinError);
+            }
+            self.high = state == PinState::High;
+            self.writes += 1;
+            Ok(())
+        }
+    }
+
+    impl StatefulOutputPin for PinStub {
+        fn is_set_high(&mut self) -> Result<bool, Self::Error> {
+            Ok(self.high)
+        }
+
+        fn is_set_low(&mut self) -> Result<bool, Self::Error> {
+            Ok(!self.high)
+        }
+    }
+
+    // PWM channel stub that remembers its duty cycle
+    #[derive(Debug)]
+    pub struct PwmStub {
+        pub duty: u16,
+        pub max_duty: u16,
+    }
+
+    impl PwmStub {
+        // Channel with a period of `max_duty` counts
+        pub fn new(max_duty: u16) -> Self {
+            Self { duty: 0, max_duty }
+        }
+    }
+
+    impl pwm::ErrorType for PwmStub {
+        type Error = Infallible;
+    }
+
+    impl SetDutyCycle for PwmStub {
+        fn max_duty_cycle(&self) -> u16 {
+            self.max_duty
+        }
+
+        fn set_duty_cycle(&mut self, duty: u16) -> Result<(), Self::Error> {
+            self.duty = duty;
+            Ok(())
+        }
+    }
+
+    // Active-high LED follows the pin level
+    #[test]
+    fn test_active_high() {
+        let mut led = Led::active_high(PinStub::default());
+        led.on().unwrap();
+        assert!(led.is_on().unwrap());
+        led.off().unwrap();
+        assert!(!led.is_on().unwrap());
+        led.set(true).unwrap();
+        assert!(led.release().high);
+    }
+
+    // Active-low LED inverts the pin level
+    #[test]
+    fn test_active_low() {
+        let mut led = Led::new(PinStub::default(), ActiveLevel::Low);
+        assert_eq!(led.active_level(), ActiveLevel::Low);
+        led.on().unwrap();
+        assert!(led.is_on().unwrap());
+        assert!(!led.pin.high);
+        led.off().unwrap();
+        assert!(!led.is_on().unwrap());
+        assert!(led.pin.high);
+    }
+
+    // Toggling flips the state from either starting point
+    #[test]
+    fn test_toggle() {
+        for active in [ActiveLevel::High, ActiveLevel::Low] {
+            let mut led = Led::new(PinStub::default(), active);
+            led.off().unwrap();
+            led.toggle().unwrap();
+            assert!(led.is_on().unwrap());
+            led.toggle().unwrap();
+            assert!(!led.is_on().unwrap());
+        }
+    }
+
+    // Pin errors are returned, not unwrapped
+    #[test]
+    fn test_errors() {
+        let mut led: Led<PinStub> = PinStub {
+            fail: true,
+            ..Default::default()
+        }
+        .into();
+        assert_eq!(led.on(), Err(PinError));
+        assert_eq!(led.toggle(), Err(PinError));
+        assert_eq!(led.release().writes, 0);
+    }
+}
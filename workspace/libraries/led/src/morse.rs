@@ -0,0 +1,24 @@
+//! International Morse code table
+
+/// Letters A to Z
+const LETTERS: [&str; 26] = [
+    ".-", "-...", "-.-.", "-..", ".", "..-.", "--.", "....", "..", ".---", "-.-", ".-..", "--",
+    "-.", "---", ".--.", "--.-", ".-.", "...", "-", "..-", "...-", ".--", "-..-", "-.--", "--..",
+];
+
+/// Digits 0 to 9
+const DIGITS: [&str; 10] = [
+    "-----", ".----", "..---", "...--", "....-", ".....", "-....", "--...", "---..", "----.",
+];
+
+/// Dots and dashes for a character (any case), or `None` for a space or
+/// an unsupported character
+pub(crate) fn code(c: u8) -> Option<&'static [u8]> {
+    let symbols = match c {
+        b'A'..=b'Z' => LETTERS[(c - b'A') as usize],
+        b'a'..=b'z' => LETTERS[(c - b'a') as usize],
+        b'0'..=b'9' => DIGITS[(c - b'0') as usize],
+        _ => return None,
+    };
+    Some(symbols.as_bytes())
+}
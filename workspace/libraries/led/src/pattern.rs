@@ -0,0 +1,445 @@
+//! Non-blocking LED patterns
+//!
+//! A [`Sequencer`] plays a [`Pattern`] one [`Step`] at a time. Nothing
+//! blocks: call [`Sequencer::drive`] with the current time and it sets the
+//! LED and returns when the next change is due, so it can be called from a
+//! superloop, rescheduled from a timer alarm interrupt, or awaited in an
+//! Embassy task:
+//!
+//! ```ignore
+//! let mut sequencer = Sequencer::new();
+//! sequencer.play(Pattern::HEARTBEAT, now_ms);
+//! loop {
+//!     let next_ms = sequencer.drive(now_ms, &mut led)?;
+//!     // sleep until `next_ms` (None: the LED holds its state)
+//! }
+//! ```
+//!
+//! Times are in milliseconds from any monotonic source; only differences
+//! matter, so host tests can use a virtual clock.
+
+use crate::{Brightness, morse};
+
+/// Brightness of a fully lit LED
+pub const FULL: u8 = 255;
+
+/// Blink codes: on time of each blink
+const CODE_ON_MS: u32 = 200;
+
+/// Blink codes: off time between blinks
+const CODE_OFF_MS: u32 = 300;
+
+/// Blink codes: pause before the code repeats
+const CODE_PAUSE_MS: u32 = 1500;
+
+/// Breathing: time between brightness changes
+const BREATHE_STEP_MS: u32 = 20;
+
+/// One step of a pattern: a brightness held for a time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    /// Brightness, 0 (off) to [`FULL`]
+    pub level: u8,
+    /// How long to hold it (0: until another pattern is played)
+    pub ms: u32,
+}
+
+/// Repeating LED pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pattern {
+    /// Steady off
+    Off,
+    /// Steady on
+    On,
+    /// On and off durations in milliseconds, starting with on (use an
+    /// even number of entries)
+    Blink(&'static [u16]),
+    /// Error code: this many blinks, then a pause
+    Code(u8),
+    /// Fade up and down once per period (needs a PWM output to look smooth)
+    Breathe {
+        /// Length of one breath in milliseconds
+        period_ms: u16,
+    },
+    /// Morse code for ASCII letters and digits; anything else is a word
+    /// gap
+    Morse {
+        /// Text to send
+        text: &'static str,
+        /// Length of a dot in milliseconds
+        unit_ms: u16,
+    },
+}
+
+impl Pattern {
+    /// Double pulse once a second, for "alive and well"
+    pub const HEARTBEAT: Pattern = Pattern::Blink(&[80, 120, 80, 720]);
+
+    /// The classic 500 ms on, 500 ms off
+    pub const BLINK: Pattern = Pattern::Blink(&[500, 500]);
+
+    /// Morse text at 100 ms per dot
+    pub const fn morse(text: &'static str) -> Self {
+        Pattern::Morse { text, unit_ms: 100 }
+    }
+
+    /// Step at the cursor, advancing the cursor to the next one
+    fn step(&self, cursor: &mut Cursor) -> Step {
+        match *self {
+            Pattern::Off => Step { level: 0, ms: 0 },
+            Pattern::On => Step { level: FULL, ms: 0 },
+            Pattern::Blink(table) => {
+                if table.is_empty() {
+                    return Step { level: 0, ms: 0 };
+                }
+                let index = cursor.index % table.len();
+                cursor.index = index + 1;
+                Step {
+                    level: if index.is_multiple_of(2) { FULL } else { 0 },
+                    ms: table[index] as u32,
+                }
+            }
+            Pattern::Code(blinks) => {
+                let len = 2 * blinks.max(1) as usize;
+                let index = cursor.index % len;
+                cursor.index = index + 1;
+                match (index.is_multiple_of(2), index + 1 == len) {
+                    (true, _) => Step {
+                        level: FULL,
+                        ms: CODE_ON_MS,
+                    },
+                    (false, false) => Step {
+                        level: 0,
+                        ms: CODE_OFF_MS,
+                    },
+                    (false, true) => Step {
+                        level: 0,
+                        ms: CODE_PAUSE_MS,
+                    },
+                }
+            }
+            Pattern::Breathe { period_ms } => {
+                // Triangle wave: up for the first half, down for the second
+                let len = (period_ms as u32 / BREATHE_STEP_MS).max(2);
+                let index = cursor.index as u32 % len;
+                cursor.index = index as usize + 1;
+                let half = len / 2;
+                let level = if index < half {
+                    FULL as u32 * index / half
+                } else {
+                    FULL as u32 * (len - index) / (len - half)
+                };
+                Step {
+                    level: level as u8,
+                    ms: BREATHE_STEP_MS,
+                }
+            }
+            Pattern::Morse { text, unit_ms } => morse_step(text.as_bytes(), unit_ms as u32, cursor),
+        }
+    }
+}
+
+/// Next Morse step: a dot or dash followed by the gap after it
+///
+/// Gaps are 1 unit within a character, 3 between characters and 7 between
+/// words (a space adds 4 to the 3 already sent).
+fn morse_step(text: &[u8], unit_ms: u32, cursor: &mut Cursor) -> Step {
+    // End of the text: word gap, then start over
+    let Some(&c) = text.get(cursor.index) else {
+        *cursor = Cursor::default();
+        let ms = if text.is_empty() { 0 } else { 4 * unit_ms };
+        return Step { level: 0, ms };
+    };
+
+    let Some(symbols) = morse::code(c) else {
+        cursor.index += 1;
+        return Step {
+            level: 0,
+            ms: 4 * unit_ms,
+        };
+    };
+
+    let element = cursor.element as usize;
+    if !cursor.gap {
+        cursor.gap = true;
+        let units = if symbols[element] == b'-' { 3 } else { 1 };
+        return Step {
+            level: FULL,
+            ms: units * unit_ms,
+        };
+    }
+
+    cursor.gap = false;
+    if element + 1 < symbols.len() {
+        cursor.element += 1;
+        Step {
+            level: 0,
+            ms: unit_ms,
+        }
+    } else {
+        cursor.index += 1;
+        cursor.element = 0;
+        Step {
+            level: 0,
+            ms: 3 * unit_ms,
+        }
+    }
+}
+
+/// Position within a pattern
+#[derive(Debug, Clone, Copy, Default)]
+struct Cursor {
+    /// Step index, or character index for Morse
+    index: usize,
+    /// Morse: dot or dash within the character
+    element: u8,
+    /// Morse: the gap after the element is next
+    gap: bool,
+}
+
+/// Plays a [`Pattern`] against a clock
+#[derive(Debug, Clone)]
+pub struct Sequencer {
+    pattern: Pattern,
+    cursor: Cursor,
+    step: Step,
+    step_end_ms: Option<u64>,
+}
+
+impl Sequencer {
+    /// Create a sequencer playing [`Pattern::Off`]
+    pub const fn new() -> Self {
+        Self {
+            pattern: Pattern::Off,
+            cursor: Cursor {
+                index: 0,
+                element: 0,
+                gap: false,
+            },
+            step: Step { level: 0, ms: 0 },
+            step_end_ms: None,
+        }
+    }
+
+    /// Start a pattern from its beginning at `now_ms`
+    pub fn play(&mut self, pattern: Pattern, now_ms: u64) {
+        self.pattern = pattern;
+        self.cursor = Cursor::default();
+        self.next_step(now_ms);
+    }
+
+    /// Pattern being played
+    pub fn pattern(&self) -> Pattern {
+        self.pattern
+    }
+
+    /// Advance to `now_ms` and return the brightness to show
+    ///
+    /// Steps that ended while nobody called are skipped, so the pattern
+    /// stays in time even if updates are late.
+    pub fn update(&mut self, now_ms: u64) -> u8 {
+        while let Some(end_ms) = self.step_end_ms {
+            if now_ms < end_ms {
+                break;
+            }
+            self.next_step(end_ms);
+        }
+        self.step.level
+    }
+
+    /// Current brightness (as of the last update)
+    pub fn level(&self) -> u8 {
+        self.step.level
+    }
+
+    /// When the brightness changes next, or `None` for a steady pattern
+    pub fn deadline_ms(&self) -> Option<u64> {
+        self.step_end_ms
+    }
+
+    /// Advance to `now_ms`, set the LED, and return the next deadline
+    pub fn drive<L: Brightness>(
+        &mut self,
+        now_ms: u64,
+        led: &mut L,
+    ) -> Result<Option<u64>, L::Error> {
+        let level = self.update(now_ms);
+        led.set_brightness(level)?;
+        Ok(self.deadline_ms())
+    }
+
+    /// Start the step at the cursor at `start_ms`
+    fn next_step(&mut self, start_ms: u64) {
+        self.step = self.pattern.step(&mut self.cursor);
+        self.step_end_ms = (self.step.ms > 0).then(|| start_ms + self.step.ms as u64);
+    }
+}
+
+impl Default for Sequencer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    // Import top-level structs/functions
+    use super::*;
+
+    // Explicitly link to std
+    extern crate std;
+    use std::vec;
+    use std::vec::Vec;
+
+    // Test-only imports
+    use crate::Led;
+    use crate::tests::PinStub;
+
+    // Play a pattern on a virtual clock, 1 ms per update, and return each
+    // brightness change as `(time_ms, level)`
+    fn record(pattern: Pattern, end_ms: u64) -> Vec<(u64, u8)> {
+        let mut sequencer = Sequencer::new();
+        sequencer.play(pattern, 0);
+        let mut changes = vec![(0, sequencer.level())];
+        for now_ms in 1..=end_ms {
+            let level = sequencer.update(now_ms);
+            if level != changes.last().unwrap().1 {
+                changes.push((now_ms, level));
+            }
+        }
+        changes
+    }
+
+    // Heartbeat: two pulses, then a long pause, repeating every second
+    #[test]
+    fn test_heartbeat() {
+        let changes = record(Pattern::HEARTBEAT, 1100);
+        assert_eq!(
+            changes,
+            [
+                (0, FULL),
+                (80, 0),
+                (200, FULL),
+                (280, 0),
+                (1000, FULL),
+                (1080, 0)
+            ]
+        );
+    }
+
+    // Error code 3: three blinks, pause, repeat
+    #[test]
+    fn test_code() {
+        let changes = record(Pattern::Code(3), 3000);
+        assert_eq!(
+            changes,
+            [
+                (0, FULL),
+                (200, 0),
+                (500, FULL),
+                (700, 0),
+                (1000, FULL),
+                (1200, 0),
+                (2700, FULL),
+                (2900, 0)
+            ]
+        );
+    }
+
+    // "SOS" at 10 ms per dot, including the word gap before it repeats
+    #[test]
+    fn test_morse() {
+        let pattern = Pattern::Morse {
+            text: "SOs",
+            unit_ms: 10,
+        };
+        let on: Vec<(u64, u64)> = record(pattern, 400)
+            .windows(2)
+            .filter(|pair| pair[0].1 == FULL)
+            .map(|pair| (pair[0].0, pair[1].0 - pair[0].0))
+            .collect();
+
+        // S = ..., O = ---, 3-unit gaps between letters, 7 before repeating
+        assert_eq!(
+            on,
+            [
+                (0, 10),
+                (20, 10),
+                (40, 10),
+                (80, 30),
+                (120, 30),
+                (160, 30),
+                (220, 10),
+                (240, 10),
+                (260, 10),
+                (340, 10),
+                (360, 10),
+                (380, 10),
+            ]
+        );
+
+        // Word gaps and unknown characters are silence
+        let mut sequencer = Sequencer::new();
+        sequencer.play(Pattern::morse("E E"), 0);
+        assert_eq!(sequencer.update(99), FULL);
+        assert_eq!(sequencer.update(100), 0);
+        assert_eq!(sequencer.update(799), 0);
+        assert_eq!(sequencer.update(800), FULL);
+    }
+
+    // Breathing ramps up to full and back down once per period
+    #[test]
+    fn test_breathe() {
+        let mut sequencer = Sequencer::new();
+        sequencer.play(Pattern::Breathe { period_ms: 2000 }, 0);
+        let levels: Vec<u8> = (0..100).map(|i| sequencer.update(i * 20)).collect();
+        assert_eq!(levels[0], 0);
+        assert_eq!(levels[50], FULL);
+        assert!(levels[..50].windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(levels[50..].windows(2).all(|pair| pair[0] > pair[1]));
+        assert_eq!(sequencer.update(2000), 0);
+    }
+
+    // Steady patterns have no deadline; late updates catch up
+    #[test]
+    fn test_deadlines() {
+        let mut sequencer = Sequencer::default();
+        assert_eq!(sequencer.update(1_000_000), 0);
+        assert_eq!(sequencer.deadline_ms(), None);
+        sequencer.play(Pattern::On, 5);
+        assert_eq!(sequencer.deadline_ms(), None);
+        assert_eq!(sequencer.level(), FULL);
+
+        // 10.2 s after starting the 1 s blink, the LED is on
+        sequencer.play(Pattern::BLINK, 1000);
+        assert_eq!(sequencer.deadline_ms(), Some(1500));
+        assert_eq!(sequencer.update(11_200), FULL);
+        assert_eq!(sequencer.deadline_ms(), Some(11_500));
+        assert_eq!(sequencer.pattern(), Pattern::BLINK);
+
+        // Empty tables hold the LED off
+        sequencer.play(Pattern::Blink(&[]), 0);
+        assert_eq!(sequencer.deadline_ms(), None);
+        sequencer.play(Pattern::morse(""), 0);
+        assert_eq!(sequencer.deadline_ms(), None);
+    }
+
+    // Driving an on/off LED lights it from half brightness up
+    #[test]
+    fn test_drive() {
+        let mut led = Led::active_low(PinStub::default());
+        let mut sequencer = Sequencer::new();
+        sequencer.play(Pattern::HEARTBEAT, 0);
+        assert_eq!(sequencer.drive(0, &mut led), Ok(Some(80)));
+        assert!(led.is_on().unwrap());
+        assert_eq!(sequencer.drive(80, &mut led), Ok(Some(200)));
+        assert!(!led.is_on().unwrap());
+
+        sequencer.play(Pattern::Breathe { period_ms: 1000 }, 0);
+        sequencer.drive(300, &mut led).unwrap();
+        assert!(led.is_on().unwrap());
+        sequencer.drive(1000, &mut led).unwrap();
+        assert!(!led.is_on().unwrap());
+    }
+}
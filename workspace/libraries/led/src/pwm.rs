@@ -0,0 +1,191 @@
+//! Dimmable LED on a PWM channel
+//!
+//! [`PwmLed`] works with any `embedded-hal` [`SetDutyCycle`] channel, such
+//! as a channel of an RP2350 or RP2040 PWM slice. The brightness is the duty
+//! cycle, or with [`PwmLed::with_gamma`] the duty cycle that looks that
+//! bright: the eye is far more sensitive to changes in dim light, so a
+//! linear ramp seems to jump at the bottom and stall at the top.
+//!
+//! ```ignore
+//! let mut pwm = pwm_slices.pwm7;
+//! pwm.enable();
+//! pwm.channel_b.output_to(pins.gpio15);
+//! let mut led = PwmLed::new(&mut pwm.channel_b).with_gamma();
+//! led.set_brightness(128)?;
+//! ```
+
+use embedded_hal::pwm::SetDutyCycle;
+
+use crate::Brightness;
+
+/// Gamma table: duty fraction (of `u16::MAX`) for each perceived level
+const GAMMA: [u16; 256] = gamma_table();
+
+/// Duty cycle, as a fraction of `u16::MAX`, that looks like `level` of 255
+///
+/// Follows the CIE 1931 lightness curve, which is linear below 8 %
+/// lightness and a cube above it.
+pub const fn gamma(level: u8) -> u16 {
+    GAMMA[level as usize]
+}
+
+// Build the gamma table at compile time (integer maths only)
+const fn gamma_table() -> [u16; 256] {
+    let mut table = [0; 256];
+    let mut level = 0;
+    while level < 256 {
+        table[level] = cie1931(level as u64);
+        level += 1;
+    }
+    table
+}
+
+// Luminance for a lightness of `level` / 255, scaled to `u16::MAX`
+const fn cie1931(level: u64) -> u16 {
+    let max = u16::MAX as u64;
+
+    // L* = 100 * level / 255; at or below 8, Y = L* / 903.3
+    if level * 100 <= 8 * 255 {
+        let den = 255 * 9033;
+        return ((max * 1000 * level + den / 2) / den) as u16;
+    }
+
+    // Otherwise Y = ((L* + 16) / 116)^3
+    let num = level * 100 + 16 * 255;
+    let den = 116 * 255;
+    let den3 = den * den * den;
+    ((max * num * num * num + den3 / 2) / den3) as u16
+}
+
+/// LED on a PWM channel
+pub struct PwmLed<C> {
+    channel: C,
+    gamma: bool,
+}
+
+impl<C> PwmLed<C>
+where
+    C: SetDutyCycle,
+{
+    /// Wrap a PWM channel (lit by a high output)
+    ///
+    /// For an LED lit by a low output, invert the channel in the HAL (e.g.
+    /// `set_inverted()` on an RP2350 channel).
+    pub fn new(channel: C) -> Self {
+        Self {
+            channel,
+            gamma: false,
+        }
+    }
+
+    /// Correct brightness levels with [`gamma`] so they look evenly spaced
+    pub fn with_gamma(mut self) -> Self {
+        self.gamma = true;
+        self
+    }
+
+    /// Set the brightness from 0 (off) to 255 (full)
+    pub fn set_brightness(&mut self, level: u8) -> Result<(), C::Error> {
+        if self.gamma {
+            self.channel.set_duty_cycle_fraction(gamma(level), u16::MAX)
+        } else {
+            self.channel.set_duty_cycle_fraction(level as u16, 255)
+        }
+    }
+
+    /// Turn the LED fully on
+    pub fn on(&mut self) -> Result<(), C::Error> {
+        self.channel.set_duty_cycle_fully_on()
+    }
+
+    /// Turn the LED off
+    pub fn off(&mut self) -> Result<(), C::Error> {
+        self.channel.set_duty_cycle_fully_off()
+    }
+
+    /// Release the channel
+    pub fn release(self) -> C {
+        self.channel
+    }
+}
+
+impl<C> Brightness for PwmLed<C>
+where
+    C: SetDutyCycle,
+{
+    type Error = C::Error;
+
+    fn set_brightness(&mut self, level: u8) -> Result<(), Self::Error> {
+        PwmLed::set_brightness(self, level)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    // Import top-level structs/functions
+    use super::*;
+
+    // Test-only imports
+    use crate::tests::PwmStub;
+    use crate::{Pattern, Sequencer};
+
+    // Channel period used by these tests
+    const MAX_DUTY: u16 = 1000;
+
+    // Brightness maps onto the channel's duty range
+    #[test]
+    fn test_brightness() {
+        let mut led = PwmLed::new(PwmStub::new(MAX_DUTY));
+        led.set_brightness(255).unwrap();
+        assert_eq!(led.channel.duty, MAX_DUTY);
+        led.set_brightness(51).unwrap();
+        assert_eq!(led.channel.duty, 200);
+        led.off().unwrap();
+        assert_eq!(led.channel.duty, 0);
+        led.on().unwrap();
+        assert_eq!(led.release().duty, MAX_DUTY);
+    }
+
+    // Gamma curve runs from off to full, is monotonic, and is continuous
+    // where the linear part meets the cube
+    #[test]
+    fn test_gamma_curve() {
+        assert_eq!(gamma(0), 0);
+        assert_eq!(gamma(255), u16::MAX);
+        for level in 1..=255 {
+            assert!(gamma(level) > gamma(level - 1), "level {}", level);
+        }
+        let step = gamma(20) - gamma(19);
+        assert!((gamma(21) - gamma(20)).abs_diff(step) <= 1);
+
+        // Half perceived brightness is under a fifth of the power
+        assert_eq!(gamma(128), 12_179);
+    }
+
+    // Gamma-corrected brightness is dimmer in the middle, equal at the ends
+    #[test]
+    fn test_gamma_brightness() {
+        let mut led = PwmLed::new(PwmStub::new(MAX_DUTY)).with_gamma();
+        led.set_brightness(0).unwrap();
+        assert_eq!(led.channel.duty, 0);
+        led.set_brightness(128).unwrap();
+        assert_eq!(led.channel.duty, 185);
+        led.set_brightness(255).unwrap();
+        assert_eq!(led.channel.duty, MAX_DUTY);
+    }
+
+    // Breathing drives intermediate duty cycles
+    #[test]
+    fn test_breathe() {
+        let mut led = PwmLed::new(PwmStub::new(MAX_DUTY));
+        let mut sequencer = Sequencer::new();
+        sequencer.play(Pattern::Breathe { period_ms: 2000 }, 0);
+
+        // A quarter of the way in: half brightness (127 of 255)
+        sequencer.drive(500, &mut led).unwrap();
+        assert_eq!(led.channel.duty, 498);
+        sequencer.drive(1000, &mut led).unwrap();
+        assert_eq!(led.channel.duty, MAX_DUTY);
+    }
+}
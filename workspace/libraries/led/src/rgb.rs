@@ -0,0 +1,214 @@
+//! RGB colors and RGB LEDs on three PWM channels
+//!
+//! [`Rgb`] is a color in perceived brightness per channel: [`RgbLed`]
+//! gamma-corrects each channel so that mixes and fades look right.
+//! [`Rgb::gradient`] maps a value in a range onto a color scale, e.g. a
+//! temperature onto blue, green and red.
+
+use embedded_hal::pwm::SetDutyCycle;
+
+use crate::PwmLed;
+use crate::fade::Lerp;
+
+/// Color with 0 to 255 per channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Rgb {
+    /// Red
+    pub r: u8,
+    /// Green
+    pub g: u8,
+    /// Blue
+    pub b: u8,
+}
+
+impl Rgb {
+    /// All channels off
+    pub const OFF: Rgb = Rgb::new(0, 0, 0);
+    /// Full red
+    pub const RED: Rgb = Rgb::new(255, 0, 0);
+    /// Full green
+    pub const GREEN: Rgb = Rgb::new(0, 255, 0);
+    /// Full blue
+    pub const BLUE: Rgb = Rgb::new(0, 0, 255);
+    /// Red and green
+    pub const YELLOW: Rgb = Rgb::new(255, 255, 0);
+    /// Green and blue
+    pub const CYAN: Rgb = Rgb::new(0, 255, 255);
+    /// Red and blue
+    pub const MAGENTA: Rgb = Rgb::new(255, 0, 255);
+    /// All channels full
+    pub const WHITE: Rgb = Rgb::new(255, 255, 255);
+
+    /// Create a color from its channels
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Same color at `level` of 255 of the brightness
+    pub fn dim(self, level: u8) -> Self {
+        Rgb::OFF.lerp(self, level as u32, 255)
+    }
+
+    /// Color `num / den` of the way along evenly spaced color stops
+    ///
+    /// The first stop is at 0 and the last at `den`; values outside the
+    /// range take the nearest end. No stops gives [`Rgb::OFF`].
+    pub fn gradient(stops: &[Rgb], num: u32, den: u32) -> Rgb {
+        let (first, last) = match (stops.first(), stops.last()) {
+            (Some(first), Some(last)) => (*first, *last),
+            _ => return Rgb::OFF,
+        };
+        if num >= den || stops.len() == 1 {
+            return if num == 0 { first } else { last };
+        }
+
+        // Position in units of `den` per segment between two stops
+        let position = num as u64 * (stops.len() - 1) as u64;
+        let segment = (position / den as u64) as usize;
+        let offset = (position % den as u64) as u32;
+        stops[segment].lerp(stops[segment + 1], offset, den)
+    }
+}
+
+impl Lerp for Rgb {
+    fn lerp(self, to: Self, num: u32, den: u32) -> Self {
+        Rgb {
+            r: self.r.lerp(to.r, num, den),
+            g: self.g.lerp(to.g, num, den),
+            b: self.b.lerp(to.b, num, den),
+        }
+    }
+}
+
+/// RGB LED on three PWM channels
+///
+/// The channels may be of different types (e.g. on different PWM slices)
+/// but must share an error type. For a common-anode LED, invert the
+/// channels in the HAL.
+pub struct RgbLed<R, G, B> {
+    red: PwmLed<R>,
+    green: PwmLed<G>,
+    blue: PwmLed<B>,
+}
+
+impl<R, G, B, E> RgbLed<R, G, B>
+where
+    R: SetDutyCycle<Error = E>,
+    G: SetDutyCycle<Error = E>,
+    B: SetDutyCycle<Error = E>,
+{
+    /// Wrap the red, green and blue channels (gamma-corrected)
+    pub fn new(red: R, green: G, blue: B) -> Self {
+        Self {
+            red: PwmLed::new(red).with_gamma(),
+            green: PwmLed::new(green).with_gamma(),
+            blue: PwmLed::new(blue).with_gamma(),
+        }
+    }
+
+    /// Show a color
+    pub fn set_color(&mut self, color: Rgb) -> Result<(), E> {
+        self.red.set_brightness(color.r)?;
+        self.green.set_brightness(color.g)?;
+        self.blue.set_brightness(color.b)
+    }
+
+    /// Turn all channels off
+    pub fn off(&mut self) -> Result<(), E> {
+        self.red.off()?;
+        self.green.off()?;
+        self.blue.off()
+    }
+
+    /// Release the red, green and blue channels
+    pub fn release(self) -> (R, G, B) {
+        (
+            self.red.release(),
+            self.green.release(),
+            self.blue.release(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    // Import top-level structs/functions
+    use super::*;
+
+    // Test-only imports
+    use crate::tests::PwmStub;
+    use crate::{Fade, gamma};
+
+    // Temperature scale used by the demo app
+    const SCALE: &[Rgb] = &[Rgb::BLUE, Rgb::GREEN, Rgb::RED];
+
+    // Colors mix channel by channel
+    #[test]
+    fn test_lerp() {
+        assert_eq!(Rgb::BLUE.lerp(Rgb::RED, 1, 2), Rgb::new(128, 0, 128));
+        assert_eq!(Rgb::RED.lerp(Rgb::YELLOW, 1, 4), Rgb::new(255, 64, 0));
+        assert_eq!(Rgb::WHITE.dim(0), Rgb::OFF);
+        assert_eq!(Rgb::WHITE.dim(255), Rgb::WHITE);
+        assert_eq!(Rgb::CYAN.dim(51), Rgb::new(0, 51, 51));
+    }
+
+    // Gradient passes through each stop and blends between them
+    #[test]
+    fn test_gradient() {
+        assert_eq!(Rgb::gradient(SCALE, 0, 20), Rgb::BLUE);
+        assert_eq!(Rgb::gradient(SCALE, 5, 20), Rgb::new(0, 128, 128));
+        assert_eq!(Rgb::gradient(SCALE, 10, 20), Rgb::GREEN);
+        assert_eq!(Rgb::gradient(SCALE, 15, 20), Rgb::new(128, 128, 0));
+        assert_eq!(Rgb::gradient(SCALE, 20, 20), Rgb::RED);
+
+        // Out of range clamps to the ends
+        assert_eq!(Rgb::gradient(SCALE, 25, 20), Rgb::RED);
+
+        // Degenerate scales
+        assert_eq!(Rgb::gradient(&[], 5, 20), Rgb::OFF);
+        assert_eq!(Rgb::gradient(&[Rgb::CYAN], 5, 20), Rgb::CYAN);
+        assert_eq!(Rgb::gradient(SCALE, 0, 0), Rgb::BLUE);
+    }
+
+    // Every step along the gradient changes each channel by at most one
+    // step's worth
+    #[test]
+    fn test_gradient_smooth() {
+        let mut previous = Rgb::gradient(SCALE, 0, 1000);
+        for num in 1..=1000 {
+            let color = Rgb::gradient(SCALE, num, 1000);
+            for (a, b) in [
+                (previous.r, color.r),
+                (previous.g, color.g),
+                (previous.b, color.b),
+            ] {
+                assert!(a.abs_diff(b) <= 1, "step {}: {:?}", num, color);
+            }
+            previous = color;
+        }
+    }
+
+    // Colors fade like brightness levels
+    #[test]
+    fn test_color_fade() {
+        let fade = Fade::new(Rgb::RED, Rgb::BLUE, 0, 100);
+        assert_eq!(fade.value(50), Rgb::new(128, 0, 128));
+        assert_eq!(fade.value(100), Rgb::BLUE);
+    }
+
+    // Each channel is gamma-corrected
+    #[test]
+    fn test_rgb_led() {
+        let mut led = RgbLed::new(
+            PwmStub::new(u16::MAX),
+            PwmStub::new(u16::MAX),
+            PwmStub::new(u16::MAX),
+        );
+        led.set_color(Rgb::new(0, 128, 255)).unwrap();
+        let (red, green, blue) = led.release();
+        assert_eq!(red.duty, 0);
+        assert_eq!(green.duty, gamma(128));
+        assert_eq!(blue.duty, u16::MAX);
+    }
+}
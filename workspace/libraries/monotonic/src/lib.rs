@@ -0,0 +1,52 @@
+#![no_std]
+
+//! # Monotonic
+//!
+//! The time source shared by the workspace libraries, so one clock can
+//! drive a debounced button and a sensor cache alike.
+//!
+//! Any closure returning microseconds is a [`Clock`], e.g. with the RP2350
+//! HAL:
+//!
+//! ```ignore
+//! let clock = || timer.get_counter().ticks();
+//! ```
+
+/// Monotonic time source in microseconds
+pub trait Clock {
+    /// Microseconds since an arbitrary fixed point
+    fn now_us(&self) -> u64;
+}
+
+impl<F> Clock for F
+where
+    F: Fn() -> u64,
+{
+    fn now_us(&self) -> u64 {
+        self()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    // Import top-level structs/functions
+    use super::*;
+
+    // Test-only imports
+    use core::cell::Cell;
+
+    // Generic code sees the time a closure returns
+    #[test]
+    fn test_closure_clock() {
+        fn elapsed<C: Clock>(clock: &C, since_us: u64) -> u64 {
+            clock.now_us() - since_us
+        }
+
+        let now_us = Cell::new(1_000);
+        let clock = || now_us.get();
+        assert_eq!(elapsed(&clock, 250), 750);
+        now_us.set(5_000);
+        assert_eq!(elapsed(&clock, 250), 4_750);
+    }
+}
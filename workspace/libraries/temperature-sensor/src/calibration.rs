@@ -0,0 +1,393 @@
+//! Calibration of sensor readings
+//!
+//! A [`Calibration`] maps a raw reading to `gain * raw + offset`. Use
+//! [`Calibration::offset`] to remove a fixed bias (e.g. self-heating in an
+//! enclosure) or [`Calibration::two_point`] to fit gain and offset from two
+//! reference temperatures. Wrap any [`TemperatureSensor`] in [`Calibrated`]
+//! to apply it to every reading.
+//!
+//! Gain and offset are Q16.16 fixed point (1/65536 of one and of a count),
+//! so correction needs no floating point. [`Calibration::to_bytes`] packs
+//! them into a 10-byte blob with a version byte and CRC-8 for storing in
+//! flash; [`Calibration::from_bytes`] rejects erased or corrupted blobs.
+
+use core::fmt;
+
+use crate::{Temperature, TemperatureSensor};
+
+/// Fixed-point one (Q16.16)
+const ONE: i64 = 1 << 16;
+
+/// Blob format version
+const VERSION: u8 = 1;
+
+/// Smallest accepted gain (0.5)
+const MIN_GAIN: i32 = 1 << 15;
+
+/// Largest accepted gain (2.0)
+const MAX_GAIN: i32 = 1 << 17;
+
+/// Error decoding a calibration blob
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalibrationError {
+    /// Blob is not [`Calibration::BYTES`] long
+    InvalidLength,
+    /// Blob was written by an unknown format version (or flash is erased)
+    UnsupportedVersion,
+    /// Checksum does not match the contents
+    BadChecksum,
+    /// Gain is outside 0.5 to 2.0, so the blob cannot be a real calibration
+    InvalidGain,
+}
+
+impl fmt::Display for CalibrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CalibrationError::InvalidLength => write!(f, "calibration blob has wrong length"),
+            CalibrationError::UnsupportedVersion => {
+                write!(f, "calibration blob version not supported")
+            }
+            CalibrationError::BadChecksum => write!(f, "calibration blob checksum mismatch"),
+            CalibrationError::InvalidGain => write!(f, "calibration gain out of range"),
+        }
+    }
+}
+
+/// Linear correction: `corrected = gain * raw + offset`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Calibration {
+    /// Gain in Q16.16
+    gain: i32,
+    /// Offset in 1/65536 counts
+    offset: i32,
+}
+
+impl Calibration {
+    /// Length of the serialized form
+    pub const BYTES: usize = 10;
+
+    /// No correction
+    pub const fn identity() -> Self {
+        Self {
+            gain: ONE as i32,
+            offset: 0,
+        }
+    }
+
+    /// Add a fixed offset to every reading
+    ///
+    /// For a sensor that reads 1.5 °C high, pass -1.5 °C.
+    pub const fn offset(offset: Temperature) -> Self {
+        Self {
+            gain: ONE as i32,
+            offset: (offset.counts() as i32) << 16,
+        }
+    }
+
+    /// Fit gain and offset from two reference points
+    ///
+    /// Each point pairs what the sensor measured with the true temperature.
+    /// Returns `None` if the measurements are equal or the fitted gain is
+    /// outside 0.5 to 2.0, which points to a mistake in the references.
+    pub fn two_point(
+        (measured_low, reference_low): (Temperature, Temperature),
+        (measured_high, reference_high): (Temperature, Temperature),
+    ) -> Option<Self> {
+        let measured_span = measured_high.counts() as i64 - measured_low.counts() as i64;
+        let reference_span = reference_high.counts() as i64 - reference_low.counts() as i64;
+        if measured_span == 0 {
+            return None;
+        }
+        let gain = div_round(reference_span * ONE, measured_span);
+        if !(MIN_GAIN as i64..=MAX_GAIN as i64).contains(&gain) {
+            return None;
+        }
+
+        // The fit goes through the low point exactly
+        let offset = reference_low.counts() as i64 * ONE - gain * measured_low.counts() as i64;
+        Some(Self {
+            gain: gain as i32,
+            offset: i32::try_from(offset).ok()?,
+        })
+    }
+
+    /// Gain in Q16.16 (65536 is 1.0)
+    pub fn gain_q16(&self) -> i32 {
+        self.gain
+    }
+
+    /// Offset, rounded to the nearest count
+    pub fn offset_temperature(&self) -> Temperature {
+        Temperature::from_counts(saturate(div_round(self.offset as i64, ONE)))
+    }
+
+    /// Correct a reading (rounded to the nearest count, saturating)
+    pub fn apply(&self, raw: Temperature) -> Temperature {
+        let corrected = self.gain as i64 * raw.counts() as i64 + self.offset as i64;
+        Temperature::from_counts(saturate(div_round(corrected, ONE)))
+    }
+
+    /// Serialize as version, gain and offset (little-endian), then CRC-8
+    pub fn to_bytes(&self) -> [u8; Self::BYTES] {
+        let mut bytes = [0u8; Self::BYTES];
+        bytes[0] = VERSION;
+        bytes[1..5].copy_from_slice(&self.gain.to_le_bytes());
+        bytes[5..9].copy_from_slice(&self.offset.to_le_bytes());
+        bytes[9] = crc8(&bytes[..9]);
+        bytes
+    }
+
+    /// Deserialize a blob written by [`Calibration::to_bytes`]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CalibrationError> {
+        let bytes: &[u8; Self::BYTES] = bytes
+            .try_into()
+            .map_err(|_| CalibrationError::InvalidLength)?;
+        if bytes[0] != VERSION {
+            return Err(CalibrationError::UnsupportedVersion);
+        }
+        if crc8(&bytes[..9]) != bytes[9] {
+            return Err(CalibrationError::BadChecksum);
+        }
+        let gain = i32::from_le_bytes([bytes[1], bytes[2], bytes[3], bytes[4]]);
+        let offset = i32::from_le_bytes([bytes[5], bytes[6], bytes[7], bytes[8]]);
+        if !(MIN_GAIN..=MAX_GAIN).contains(&gain) {
+            return Err(CalibrationError::InvalidGain);
+        }
+        Ok(Self { gain, offset })
+    }
+}
+
+impl Default for Calibration {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+/// Sensor whose readings are corrected by a [`Calibration`]
+pub struct Calibrated<S> {
+    sensor: S,
+    calibration: Calibration,
+}
+
+impl<S> Calibrated<S> {
+    /// Apply a calibration to a sensor's readings
+    pub fn new(sensor: S, calibration: Calibration) -> Self {
+        Self {
+            sensor,
+            calibration,
+        }
+    }
+
+    /// Calibration in use
+    pub fn calibration(&self) -> Calibration {
+        self.calibration
+    }
+
+    /// Replace the calibration, e.g. after a new two-point fit
+    pub fn set_calibration(&mut self, calibration: Calibration) {
+        self.calibration = calibration;
+    }
+
+    /// Access the sensor, e.g. for uncorrected readings or configuration
+    pub fn sensor_mut(&mut self) -> &mut S {
+        &mut self.sensor
+    }
+
+    /// Release the sensor
+    pub fn release(self) -> S {
+        self.sensor
+    }
+}
+
+impl<S> TemperatureSensor for Calibrated<S>
+where
+    S: TemperatureSensor,
+{
+    type Error = S::Error;
+
+    fn read_temperature(&mut self) -> Result<Temperature, Self::Error> {
+        let raw = self.sensor.read_temperature()?;
+        Ok(self.calibration.apply(raw))
+    }
+}
+
+#[cfg(feature = "async")]
+impl<S> crate::TemperatureSensorAsync for Calibrated<S>
+where
+    S: crate::TemperatureSensorAsync,
+{
+    type Error = S::Error;
+
+    async fn read_temperature(&mut self) -> Result<Temperature, Self::Error> {
+        let raw = self.sensor.read_temperature().await?;
+        Ok(self.calibration.apply(raw))
+    }
+}
+
+/// Divide, rounding halves away from zero
+fn div_round(num: i64, den: i64) -> i64 {
+    if (num < 0) != (den < 0) {
+        (num - den / 2) / den
+    } else {
+        (num + den / 2) / den
+    }
+}
+
+/// Clamp to the range of the counts type
+fn saturate(counts: i64) -> i16 {
+    counts.clamp(i16::MIN as i64, i16::MAX as i64) as i16
+}
+
+/// CRC-8 with polynomial 0x07 (SMBus PEC)
+fn crc8(bytes: &[u8]) -> u8 {
+    let mut crc = 0u8;
+    for &byte in bytes {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x07
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+#[cfg(test)]
+mod tests {
+
+    // Import top-level structs/functions
+    use super::*;
+
+    // Test-only imports
+    use crate::SimSensor;
+
+    // Shorthand for milli-degrees
+    fn mc(millicelsius: i32) -> Temperature {
+        Temperature::from_millicelsius(millicelsius)
+    }
+
+    // A single offset shifts every reading
+    #[test]
+    fn test_offset() {
+        let calibration = Calibration::offset(mc(-1_500));
+        assert_eq!(calibration.apply(mc(25_000)), mc(23_500));
+        assert_eq!(calibration.apply(mc(-10_000)), mc(-11_500));
+        assert_eq!(calibration.offset_temperature(), mc(-1_500));
+        assert_eq!(Calibration::identity().apply(mc(25_062)), mc(25_062));
+    }
+
+    // Two-point fit hits both references and interpolates between them
+    #[test]
+    fn test_two_point() {
+        // Ice bath reads 0.5 °C, 60 °C bath reads 59.0 °C
+        let calibration =
+            Calibration::two_point((mc(500), mc(0)), (mc(59_000), mc(60_000))).unwrap();
+        assert_eq!(calibration.apply(mc(500)), mc(0));
+        assert_eq!(calibration.apply(mc(59_000)), mc(60_000));
+
+        // Halfway between the measurements is halfway between the references
+        assert_eq!(calibration.apply(mc(29_750)), mc(30_000));
+
+        // Gain is 60 / 58.5
+        let expected = (60.0 / 58.5 * 65536.0_f64).round() as i32;
+        assert_eq!(calibration.gain_q16(), expected);
+    }
+
+    // Impossible reference points are rejected
+    #[test]
+    fn test_two_point_invalid() {
+        let same = (mc(25_000), mc(25_000));
+        assert_eq!(Calibration::two_point(same, same), None);
+        assert_eq!(
+            Calibration::two_point((mc(0), mc(0)), (mc(10_000), mc(50_000))),
+            None
+        );
+    }
+
+    // Corrections saturate instead of wrapping
+    #[test]
+    fn test_saturation() {
+        let calibration = Calibration::offset(Temperature::from_counts(i16::MAX));
+        let corrected = calibration.apply(Temperature::from_counts(100));
+        assert_eq!(corrected.counts(), i16::MAX);
+    }
+
+    // Blob round-trips and detects damage
+    #[test]
+    fn test_bytes() {
+        let calibration =
+            Calibration::two_point((mc(-20_000), mc(-19_250)), (mc(80_000), mc(81_000))).unwrap();
+        let bytes = calibration.to_bytes();
+        assert_eq!(bytes[0], VERSION);
+        assert_eq!(Calibration::from_bytes(&bytes), Ok(calibration));
+
+        // Any flipped bit is caught
+        for i in 1..Calibration::BYTES {
+            let mut damaged = bytes;
+            damaged[i] ^= 0x10;
+            assert_eq!(
+                Calibration::from_bytes(&damaged),
+                Err(CalibrationError::BadChecksum)
+            );
+        }
+
+        // Erased flash and short reads
+        assert_eq!(
+            Calibration::from_bytes(&[0xFF; Calibration::BYTES]),
+            Err(CalibrationError::UnsupportedVersion)
+        );
+        assert_eq!(
+            Calibration::from_bytes(&bytes[..9]),
+            Err(CalibrationError::InvalidLength)
+        );
+    }
+
+    // Blob with a valid checksum but a nonsense gain is rejected
+    #[test]
+    fn test_bytes_invalid_gain() {
+        let mut bytes = [0u8; Calibration::BYTES];
+        bytes[0] = VERSION;
+        bytes[9] = crc8(&bytes[..9]);
+        assert_eq!(
+            Calibration::from_bytes(&bytes),
+            Err(CalibrationError::InvalidGain)
+        );
+    }
+
+    // Wrapper corrects readings from any sensor
+    #[test]
+    fn test_calibrated_sensor() {
+        let sensor = SimSensor::new(mc(26_500));
+        let mut calibrated = Calibrated::new(sensor, Calibration::offset(mc(-1_500)));
+        assert_eq!(calibrated.read_temperature(), Ok(mc(25_000)));
+
+        // Raw readings are still available
+        assert_eq!(calibrated.sensor_mut().read_temperature(), Ok(mc(26_500)));
+        calibrated.set_calibration(Calibration::identity());
+        assert_eq!(calibrated.read_temperature(), Ok(mc(26_500)));
+    }
+
+    // Calibrated TMP102 against reference points through the simulator
+    #[cfg(feature = "tmp102")]
+    #[test]
+    fn test_calibrated_tmp102() {
+        use core::cell::RefCell;
+        use embedded_hal_bus::i2c::RefCellDevice;
+        use tmp102_driver::sim::Tmp102Sim;
+        use tmp102_driver::{Address, TMP102};
+
+        // This sensor reads 0.75 °C high at 0 °C and 0.25 °C low at 50 °C
+        let calibration =
+            Calibration::two_point((mc(750), mc(0)), (mc(49_750), mc(50_000))).unwrap();
+        let bus = RefCell::new(Tmp102Sim::new(Address::Ground));
+        let tmp102 = TMP102::new(RefCellDevice::new(&bus), Address::Ground);
+        let mut calibrated = Calibrated::new(tmp102, calibration);
+
+        for (raw, reference) in [(750, 0), (49_750, 50_000), (25_250, 25_000)] {
+            bus.borrow_mut().set_temperature(mc(raw));
+            assert_eq!(calibrated.read_temperature().unwrap(), mc(reference));
+        }
+    }
+}
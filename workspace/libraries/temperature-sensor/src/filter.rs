@@ -0,0 +1,454 @@
+//! Filters and rolling statistics for readings
+//!
+//! All filters keep their history in fixed-capacity `heapless` buffers and
+//! work in the sensor's 1/16 °C counts, so there is no heap and no floating
+//! point. The window length `N` is a const generic, e.g. a one-minute
+//! window at one reading per second is `RollingStats<60>`.
+//!
+//! Results are rounded to the nearest count (halves away from zero).
+
+use heapless::Deque;
+
+use crate::Temperature;
+
+/// Smooths a stream of readings one sample at a time
+pub trait Filter {
+    /// Add a sample and return the filtered value
+    fn update(&mut self, sample: Temperature) -> Temperature;
+
+    /// Forget all history
+    fn reset(&mut self);
+}
+
+/// Mean of the last `N` samples
+#[derive(Debug, Clone)]
+pub struct MovingAverage<const N: usize> {
+    window: Deque<Temperature, N>,
+    sum: i64,
+}
+
+impl<const N: usize> MovingAverage<N> {
+    /// Create an empty filter
+    pub const fn new() -> Self {
+        Self {
+            window: Deque::new(),
+            sum: 0,
+        }
+    }
+
+    /// Mean of the samples in the window, if any
+    pub fn average(&self) -> Option<Temperature> {
+        let len = self.window.len() as i64;
+        (len > 0).then(|| from_counts(div_round(self.sum, len)))
+    }
+
+    /// Number of samples in the window
+    pub fn len(&self) -> usize {
+        self.window.len()
+    }
+
+    /// Whether the window holds no samples
+    pub fn is_empty(&self) -> bool {
+        self.window.is_empty()
+    }
+
+    /// Whether the window holds `N` samples
+    pub fn is_full(&self) -> bool {
+        self.window.is_full()
+    }
+}
+
+impl<const N: usize> Default for MovingAverage<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Filter for MovingAverage<N> {
+    fn update(&mut self, sample: Temperature) -> Temperature {
+        if let Some(oldest) = push(&mut self.window, sample) {
+            self.sum -= oldest.counts() as i64;
+        }
+        self.sum += sample.counts() as i64;
+        self.average().unwrap_or(sample)
+    }
+
+    fn reset(&mut self) {
+        self.window.clear();
+        self.sum = 0;
+    }
+}
+
+/// Median of the last `N` samples (rejects single-sample spikes)
+///
+/// With an even number of samples, the mean of the middle two is used.
+#[derive(Debug, Clone)]
+pub struct Median<const N: usize> {
+    window: Deque<Temperature, N>,
+}
+
+impl<const N: usize> Median<N> {
+    /// Create an empty filter
+    pub const fn new() -> Self {
+        Self {
+            window: Deque::new(),
+        }
+    }
+
+    /// Median of the samples in the window, if any
+    pub fn median(&self) -> Option<Temperature> {
+        let len = self.window.len();
+        if len == 0 {
+            return None;
+        }
+
+        // Sort a copy so the window keeps arrival order
+        let mut sorted = [Temperature::default(); N];
+        for (dst, src) in sorted.iter_mut().zip(self.window.iter()) {
+            *dst = *src;
+        }
+        let sorted = &mut sorted[..len];
+        sorted.sort_unstable();
+
+        let upper = sorted[len / 2].counts() as i64;
+        if len % 2 == 1 {
+            return Some(from_counts(upper));
+        }
+        let lower = sorted[len / 2 - 1].counts() as i64;
+        Some(from_counts(div_round(lower + upper, 2)))
+    }
+}
+
+impl<const N: usize> Default for Median<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Filter for Median<N> {
+    fn update(&mut self, sample: Temperature) -> Temperature {
+        push(&mut self.window, sample);
+        self.median().unwrap_or(sample)
+    }
+
+    fn reset(&mut self) {
+        self.window.clear();
+    }
+}
+
+/// Exponential moving average: `y += alpha * (x - y)`
+///
+/// Needs no window. Alpha is given in thousandths; smaller values smooth
+/// more but follow real changes more slowly. The first sample is taken
+/// as-is.
+#[derive(Debug, Clone)]
+pub struct Ema {
+    alpha_permille: i64,
+    /// Filtered value in thousandths of a count
+    state: Option<i64>,
+}
+
+impl Ema {
+    /// Create a filter with alpha in thousandths (clamped to 1..=1000)
+    pub const fn new(alpha_permille: u16) -> Self {
+        let alpha = if alpha_permille == 0 {
+            1
+        } else if alpha_permille > 1000 {
+            1000
+        } else {
+            alpha_permille
+        };
+        Self {
+            alpha_permille: alpha as i64,
+            state: None,
+        }
+    }
+
+    /// Current filtered value, if any sample has been seen
+    pub fn value(&self) -> Option<Temperature> {
+        self.state.map(|state| from_counts(div_round(state, 1000)))
+    }
+}
+
+impl Filter for Ema {
+    fn update(&mut self, sample: Temperature) -> Temperature {
+        let x = sample.counts() as i64 * 1000;
+        let y = match self.state {
+            Some(y) => y + div_round(self.alpha_permille * (x - y), 1000),
+            None => x,
+        };
+        self.state = Some(y);
+        from_counts(div_round(y, 1000))
+    }
+
+    fn reset(&mut self) {
+        self.state = None;
+    }
+}
+
+/// Summary of a [`RollingStats`] window
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    /// Number of samples
+    pub count: usize,
+    /// Lowest sample
+    pub min: Temperature,
+    /// Highest sample
+    pub max: Temperature,
+    /// Mean of the samples
+    pub mean: Temperature,
+    /// Population standard deviation in milli-degrees Celsius
+    pub stddev_millicelsius: u32,
+}
+
+/// Min, max, mean and standard deviation of the last `N` samples
+#[derive(Debug, Clone)]
+pub struct RollingStats<const N: usize> {
+    window: Deque<Temperature, N>,
+}
+
+impl<const N: usize> RollingStats<N> {
+    /// Create an empty window
+    pub const fn new() -> Self {
+        Self {
+            window: Deque::new(),
+        }
+    }
+
+    /// Add a sample, dropping the oldest once the window is full
+    pub fn push(&mut self, sample: Temperature) {
+        push(&mut self.window, sample);
+    }
+
+    /// Most recent sample
+    pub fn latest(&self) -> Option<Temperature> {
+        self.window.back().copied()
+    }
+
+    /// Statistics over the window, if it holds any samples
+    pub fn stats(&self) -> Option<Stats> {
+        let min = *self.window.iter().min()?;
+        let max = *self.window.iter().max()?;
+        let count = self.window.len();
+        let n = count as i64;
+        let (sum, sum_sq) = self.window.iter().fold((0i64, 0i64), |(s, sq), t| {
+            let c = t.counts() as i64;
+            (s + c, sq + c * c)
+        });
+
+        // Variance in counts² is (n·Σx² - (Σx)²) / n². One count is
+        // 62.5 m°C, so scale by 62.5² = 15625 / 4 to get m°C². The
+        // products outgrow 64 bits for wide windows, so work in 128.
+        let wide_n = n as i128;
+        let spread = (wide_n * sum_sq as i128 - sum as i128 * sum as i128) as u128;
+        let variance = (spread * 15_625 / (4 * (wide_n * wide_n) as u128)) as u64;
+
+        Some(Stats {
+            count,
+            min,
+            max,
+            mean: from_counts(div_round(sum, n)),
+            stddev_millicelsius: isqrt(variance) as u32,
+        })
+    }
+
+    /// Number of samples in the window
+    pub fn len(&self) -> usize {
+        self.window.len()
+    }
+
+    /// Whether the window holds no samples
+    pub fn is_empty(&self) -> bool {
+        self.window.is_empty()
+    }
+
+    /// Forget all samples
+    pub fn clear(&mut self) {
+        self.window.clear();
+    }
+}
+
+impl<const N: usize> Default for RollingStats<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Append to a window, returning the sample pushed out if it was full
+fn push<const N: usize>(
+    window: &mut Deque<Temperature, N>,
+    sample: Temperature,
+) -> Option<Temperature> {
+    let oldest = if window.is_full() {
+        window.pop_front()
+    } else {
+        None
+    };
+    // Cannot fail: there is room after the pop (and N = 0 holds nothing)
+    let _ = window.push_back(sample);
+    oldest
+}
+
+/// Divide, rounding halves away from zero
+fn div_round(num: i64, den: i64) -> i64 {
+    if (num < 0) != (den < 0) {
+        (num - den / 2) / den
+    } else {
+        (num + den / 2) / den
+    }
+}
+
+/// Counts back to a temperature (always in range for averages of counts)
+fn from_counts(counts: i64) -> Temperature {
+    Temperature::from_counts(counts as i16)
+}
+
+/// Integer square root (floor), by Newton's method
+fn isqrt(n: u64) -> u64 {
+    if n < 2 {
+        return n;
+    }
+    let mut x = n;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    x
+}
+
+#[cfg(test)]
+mod tests {
+
+    // Import top-level structs/functions
+    use super::*;
+
+    // Shorthand for whole and fractional degrees
+    fn c(celsius: i16) -> Temperature {
+        Temperature::from_celsius(celsius)
+    }
+
+    fn mc(millicelsius: i32) -> Temperature {
+        Temperature::from_millicelsius(millicelsius)
+    }
+
+    // Average covers only the last N samples
+    #[test]
+    fn test_moving_average() {
+        let mut filter = MovingAverage::<3>::new();
+        assert_eq!(filter.average(), None);
+        assert_eq!(filter.update(c(10)), c(10));
+        assert_eq!(filter.update(c(20)), c(15));
+        assert_eq!(filter.update(c(30)), c(20));
+        assert!(filter.is_full());
+        assert_eq!(filter.update(c(40)), c(30));
+        assert_eq!(filter.len(), 3);
+
+        // Negative averages round to the nearest count
+        filter.reset();
+        filter.update(Temperature::from_counts(-1));
+        assert_eq!(filter.update(Temperature::from_counts(-2)).counts(), -2);
+        assert_eq!(filter.update(Temperature::from_counts(-2)).counts(), -2);
+    }
+
+    // Median ignores a single spike
+    #[test]
+    fn test_median() {
+        let mut filter = Median::<5>::new();
+        for celsius in [25, 25, 85, 25, 26] {
+            filter.update(c(celsius));
+        }
+        assert_eq!(filter.median(), Some(c(25)));
+
+        // Even count averages the middle two
+        let mut filter = Median::<4>::new();
+        for celsius in [20, 40, 21, 22] {
+            filter.update(c(celsius));
+        }
+        assert_eq!(filter.median(), Some(mc(21_500)));
+    }
+
+    // EMA steps a fraction of the way toward each sample
+    #[test]
+    fn test_ema() {
+        let mut filter = Ema::new(500);
+        assert_eq!(filter.value(), None);
+        assert_eq!(filter.update(c(0)), c(0));
+        assert_eq!(filter.update(c(16)), c(8));
+        assert_eq!(filter.update(c(16)), c(12));
+
+        // Alpha of 1 follows the input exactly
+        let mut filter = Ema::new(1000);
+        filter.update(c(0));
+        assert_eq!(filter.update(c(-40)), c(-40));
+
+        // Settles on a constant input
+        let mut filter = Ema::new(100);
+        filter.update(c(0));
+        for _ in 0..200 {
+            filter.update(c(30));
+        }
+        assert_eq!(filter.value(), Some(c(30)));
+    }
+
+    // Statistics over a rolling window
+    #[test]
+    fn test_rolling_stats() {
+        let mut stats = RollingStats::<4>::new();
+        assert_eq!(stats.stats(), None);
+        for celsius in [99, 20, 22, 24, 26] {
+            stats.push(c(celsius));
+        }
+        let result = stats.stats().unwrap();
+        assert_eq!(result.count, 4);
+        assert_eq!(result.min, c(20));
+        assert_eq!(result.max, c(26));
+        assert_eq!(result.mean, c(23));
+        assert_eq!(stats.latest(), Some(c(26)));
+
+        // Population stddev of 20, 22, 24, 26 is sqrt(5) = 2.236 °C
+        assert_eq!(result.stddev_millicelsius, 2236);
+    }
+
+    // Constant input has no spread
+    #[test]
+    fn test_stddev_zero() {
+        let mut stats = RollingStats::<8>::new();
+        for _ in 0..8 {
+            stats.push(mc(-12_500));
+        }
+        let result = stats.stats().unwrap();
+        assert_eq!(result.stddev_millicelsius, 0);
+        assert_eq!(result.mean, mc(-12_500));
+    }
+
+    // Wide windows at the ends of the range do not overflow
+    #[test]
+    fn test_wide_window() {
+        let mut filter = MovingAverage::<{ 1 << 17 }>::new();
+        while !filter.is_full() {
+            filter.update(Temperature::from_counts(i16::MAX));
+        }
+        assert_eq!(filter.average(), Some(Temperature::from_counts(i16::MAX)));
+
+        // Alternating extremes: stddev is 32767.5 counts = 2047968.75 m°C
+        let mut stats = RollingStats::<4096>::new();
+        for i in 0..4096 {
+            let counts = if i % 2 == 0 { i16::MIN } else { i16::MAX };
+            stats.push(Temperature::from_counts(counts));
+        }
+        let result = stats.stats().unwrap();
+        assert_eq!(result.mean, Temperature::from_counts(-1));
+        assert_eq!(result.stddev_millicelsius, 2_047_968);
+    }
+
+    // Integer square root is exact at squares and floors in between
+    #[test]
+    fn test_isqrt() {
+        for n in 0..1000u64 {
+            let r = isqrt(n);
+            assert!(r * r <= n && (r + 1) * (r + 1) > n, "isqrt({})", n);
+        }
+        assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+    }
+}
@@ -0,0 +1,155 @@
+#![no_std]
+
+//! # Temperature Sensor
+//!
+//! Sensor-agnostic temperature reading, so application logic (reporting,
+//! alarms, logging) can be written once and tested on the host.
+//!
+//! Implementations:
+//!
+//! - `tmp102-driver` sensors in continuous mode (TMP102, TMP112, TMP75
+//!   and LM75), blocking and async (enable with the `tmp102` feature)
+//! - The RP2350 on-die temperature sensor, see [`Rp2350Adc`]
+//! - A simulated sensor for host tests (enable with the `sim` feature)
+//!
+//! Readings use the fixed-point [`Temperature`] type from the shared
+//! `temperature` crate, which the TMP102 driver uses too. The
+//! [`filter`] module smooths them and keeps rolling statistics, and
+//! [`Calibrated`] corrects them for a known bias or gain error.
+
+use core::fmt;
+
+pub use temperature::Temperature;
+
+// Filters and rolling statistics
+pub mod filter;
+pub use filter::{Ema, Filter, Median, MovingAverage, RollingStats, Stats};
+
+// Offset and two-point calibration
+mod calibration;
+pub use calibration::{Calibrated, Calibration, CalibrationError};
+
+// Implementations for the tmp102-driver sensors
+#[cfg(feature = "tmp102")]
+mod tmp102;
+
+// RP2350 on-die sensor
+mod rp2350;
+pub use rp2350::{RawAdc, Rp2350Adc};
+
+// Simulated sensor for host tests (enable with the `sim` feature)
+#[cfg(any(test, feature = "sim"))]
+mod sim;
+#[cfg(any(test, feature = "sim"))]
+pub use sim::{SimError, SimSensor};
+
+/// Anything that can measure a temperature
+pub trait TemperatureSensor {
+    /// Error returned by a failed reading
+    type Error;
+
+    /// Take a reading
+    fn read_temperature(&mut self) -> Result<Temperature, Self::Error>;
+}
+
+impl<T> TemperatureSensor for &mut T
+where
+    T: TemperatureSensor + ?Sized,
+{
+    type Error = T::Error;
+
+    fn read_temperature(&mut self) -> Result<Temperature, Self::Error> {
+        T::read_temperature(self)
+    }
+}
+
+/// Async version of [`TemperatureSensor`] (enable with the `async` feature)
+#[cfg(feature = "async")]
+#[allow(async_fn_in_trait)]
+pub trait TemperatureSensorAsync {
+    /// Error returned by a failed reading
+    type Error;
+
+    /// Take a reading
+    async fn read_temperature(&mut self) -> Result<Temperature, Self::Error>;
+}
+
+#[cfg(feature = "async")]
+impl<T> TemperatureSensorAsync for &mut T
+where
+    T: TemperatureSensorAsync + ?Sized,
+{
+    type Error = T::Error;
+
+    async fn read_temperature(&mut self) -> Result<Temperature, Self::Error> {
+        T::read_temperature(self).await
+    }
+}
+
+/// Take a reading and write it as one line for a serial console
+///
+/// Prints `Temperature: 25.00 deg C` or `Error: <message>`, ending in CRLF.
+pub fn write_reading<S, W>(sensor: &mut S, out: &mut W) -> fmt::Result
+where
+    S: TemperatureSensor,
+    S::Error: fmt::Display,
+    W: fmt::Write,
+{
+    write_result(&sensor.read_temperature(), out)
+}
+
+/// Write a reading taken some other way (e.g. a polled one-shot conversion)
+///
+/// Same format as [`write_reading`].
+pub fn write_result<E, W>(result: &Result<Temperature, E>, out: &mut W) -> fmt::Result
+where
+    E: fmt::Display,
+    W: fmt::Write,
+{
+    match result {
+        Ok(temp) => write!(out, "Temperature: {:.2} deg C\r\n", temp),
+        Err(e) => write!(out, "Error: {}\r\n", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    // Import top-level structs/functions
+    use super::*;
+
+    // Explicitly link to std
+    extern crate std;
+    use std::string::String;
+
+    // Application logic written against the trait, as an app would
+    fn over_limit<S: TemperatureSensor>(sensor: &mut S, limit: Temperature) -> bool {
+        matches!(sensor.read_temperature(), Ok(temp) if temp >= limit)
+    }
+
+    // Generic code works with any sensor, including through a reference
+    #[test]
+    fn test_generic_logic() {
+        let mut sensor = SimSensor::new(Temperature::from_celsius(25));
+        let limit = Temperature::from_celsius(30);
+        assert!(!over_limit(&mut sensor, limit));
+        sensor.set_temperature(Temperature::from_celsius(31));
+        assert!(over_limit(&mut &mut sensor, limit));
+        sensor.fail_next_read();
+        assert!(!over_limit(&mut sensor, limit));
+    }
+
+    // Readings and errors print one line each
+    #[test]
+    fn test_write_reading() {
+        let mut sensor = SimSensor::new(Temperature::from_millicelsius(25_500));
+        let mut out = String::new();
+        write_reading(&mut sensor, &mut out).unwrap();
+        assert_eq!(out, "Temperature: 25.50 deg C\r\n");
+
+        out.clear();
+        sensor.fail_next_read();
+        write_reading(&mut sensor, &mut out).unwrap();
+        assert_eq!(out, "Error: simulated sensor failure\r\n");
+    }
+}
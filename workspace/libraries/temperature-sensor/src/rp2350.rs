@@ -0,0 +1,152 @@
+//! RP2350 on-die temperature sensor
+//!
+//! The sensor is a diode on ADC channel 4 on the RP2350A (as on the Pico 2)
+//! and the RP2040, or channel 8 on the RP2350B; all use the same formula.
+//! Its voltage is 0.706 V at 27 °C and falls by 1.721 mV/°C, so a reading
+//! is only good to a few degrees and depends on the ADC reference being
+//! accurate.
+//!
+//! This crate does not depend on a HAL. Pass anything that returns a raw
+//! 12-bit conversion, e.g. a closure over the HAL's ADC and temperature
+//! sensor channel:
+//!
+//! ```ignore
+//! let mut adc = hal::Adc::new(pac.ADC, &mut pac.RESETS);
+//! let mut channel = adc.take_temp_sensor().unwrap();
+//! let mut sensor = Rp2350Adc::new(|| nb::block!(adc.read(&mut channel)));
+//! ```
+
+use crate::{Temperature, TemperatureSensor};
+
+/// Sensor voltage at 27 °C in microvolts
+const V_27C_UV: i64 = 706_000;
+
+/// Sensor slope in microvolts per degree Celsius (negative)
+const SLOPE_UV_PER_C: i64 = 1_721;
+
+/// Full-scale count of the 12-bit ADC
+const ADC_COUNTS: i64 = 4096;
+
+/// Source of raw ADC conversions
+///
+/// Implemented for closures returning `Result<u16, E>`.
+pub trait RawAdc {
+    /// Error returned by a failed conversion
+    type Error;
+
+    /// Run one conversion and return the raw 12-bit result
+    fn read_raw(&mut self) -> Result<u16, Self::Error>;
+}
+
+impl<F, E> RawAdc for F
+where
+    F: FnMut() -> Result<u16, E>,
+{
+    type Error = E;
+
+    fn read_raw(&mut self) -> Result<u16, Self::Error> {
+        self()
+    }
+}
+
+/// On-die temperature sensor read through the ADC
+pub struct Rp2350Adc<A> {
+    adc: A,
+    vref_mv: u32,
+}
+
+impl<A> Rp2350Adc<A>
+where
+    A: RawAdc,
+{
+    /// Default ADC reference (3.3 V supply on the Pico 2)
+    pub const DEFAULT_VREF_MV: u32 = 3300;
+
+    /// Create with the default 3.3 V reference
+    pub fn new(adc: A) -> Self {
+        Self::with_vref(adc, Self::DEFAULT_VREF_MV)
+    }
+
+    /// Create with a measured ADC reference voltage
+    pub fn with_vref(adc: A, vref_mv: u32) -> Self {
+        Self { adc, vref_mv }
+    }
+
+    /// Convert a raw ADC result to a temperature
+    pub fn raw_to_temperature(&self, raw: u16) -> Temperature {
+        // T = 27 - (V - 0.706) / 0.001721, all in integer micro-units
+        let uv = raw as i64 * self.vref_mv as i64 * 1000 / ADC_COUNTS;
+        let millicelsius = 27_000 - (uv - V_27C_UV) * 1000 / SLOPE_UV_PER_C;
+        Temperature::from_millicelsius(millicelsius as i32)
+    }
+
+    /// Release the ADC source
+    pub fn release(self) -> A {
+        self.adc
+    }
+}
+
+impl<A> TemperatureSensor for Rp2350Adc<A>
+where
+    A: RawAdc,
+{
+    type Error = A::Error;
+
+    fn read_temperature(&mut self) -> Result<Temperature, Self::Error> {
+        let raw = self.adc.read_raw()?;
+        Ok(self.raw_to_temperature(raw))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    // Import top-level structs/functions
+    use super::*;
+
+    // Raw count for a sensor voltage at 3.3 V reference (rounded)
+    fn raw_for(uv: i64) -> u16 {
+        ((uv * ADC_COUNTS + 1_650_000) / 3_300_000) as u16
+    }
+
+    // Datasheet points convert within one ADC step (about 0.47 °C)
+    #[test]
+    fn test_conversion() {
+        let adc = Rp2350Adc::new(|| Ok::<u16, ()>(0));
+        let step = Temperature::from_millicelsius(470);
+        let cases = [
+            (706_000, 27_000),
+            (706_000 - 1_721 * 10, 37_000),
+            (706_000 + 1_721 * 27, 0),
+        ];
+        for (uv, millicelsius) in cases {
+            let temp = adc.raw_to_temperature(raw_for(uv));
+            let expected = Temperature::from_millicelsius(millicelsius);
+            assert!(
+                (temp.counts() - expected.counts()).abs() <= step.counts(),
+                "{} uV: got {}, expected {}",
+                uv,
+                temp,
+                expected
+            );
+        }
+    }
+
+    // A lower reference means fewer volts per count
+    #[test]
+    fn test_vref() {
+        let default = Rp2350Adc::new(|| Ok::<u16, ()>(876));
+        let lower = Rp2350Adc::with_vref(|| Ok::<u16, ()>(876), 3000);
+        assert!(lower.raw_to_temperature(876) > default.raw_to_temperature(876));
+    }
+
+    // Readings go through the ADC source; its errors are passed through
+    #[test]
+    fn test_read() {
+        let mut results = [Ok(876), Err("busy")].into_iter();
+        let mut sensor = Rp2350Adc::new(move || results.next().unwrap());
+        let temp = sensor.read_temperature().unwrap();
+        assert_eq!(temp, sensor.raw_to_temperature(876));
+        assert_eq!(sensor.read_temperature(), Err("busy"));
+    }
+}
@@ -0,0 +1,122 @@
+//! Simulated sensor for host tests
+//!
+//! [`SimSensor`] returns a temperature set by the test, optionally drifting
+//! by a fixed step on every reading, and can be told to fail. Use it to
+//! test application logic written against [`TemperatureSensor`] without a
+//! bus or a driver.
+
+use core::fmt;
+
+use crate::{Temperature, TemperatureSensor};
+
+/// Error returned by [`SimSensor`] when told to fail
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SimError;
+
+impl fmt::Display for SimError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "simulated sensor failure")
+    }
+}
+
+/// Simulated temperature sensor
+#[derive(Debug, Clone)]
+pub struct SimSensor {
+    temperature: Temperature,
+    step: i16,
+    fail_next: bool,
+    reads: u32,
+}
+
+impl SimSensor {
+    /// Create a sensor that reads a constant temperature
+    pub fn new(temperature: Temperature) -> Self {
+        Self {
+            temperature,
+            step: 0,
+            fail_next: false,
+            reads: 0,
+        }
+    }
+
+    /// Set the temperature returned by the next reading
+    pub fn set_temperature(&mut self, temperature: Temperature) {
+        self.temperature = temperature;
+    }
+
+    /// Change the temperature by `step` after every reading (a ramp)
+    pub fn set_drift(&mut self, step: Temperature) {
+        self.step = step.counts();
+    }
+
+    /// Make the next reading fail with [`SimError`]
+    pub fn fail_next_read(&mut self) {
+        self.fail_next = true;
+    }
+
+    /// Number of readings taken, including failed ones
+    pub fn reads(&self) -> u32 {
+        self.reads
+    }
+
+    /// Take a reading, shared by the blocking and async traits
+    fn read(&mut self) -> Result<Temperature, SimError> {
+        self.reads += 1;
+        if core::mem::take(&mut self.fail_next) {
+            return Err(SimError);
+        }
+        let temperature = self.temperature;
+        self.temperature =
+            Temperature::from_counts(self.temperature.counts().saturating_add(self.step));
+        Ok(temperature)
+    }
+}
+
+impl TemperatureSensor for SimSensor {
+    type Error = SimError;
+
+    fn read_temperature(&mut self) -> Result<Temperature, Self::Error> {
+        self.read()
+    }
+}
+
+#[cfg(feature = "async")]
+impl crate::TemperatureSensorAsync for SimSensor {
+    type Error = SimError;
+
+    async fn read_temperature(&mut self) -> Result<Temperature, Self::Error> {
+        self.read()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    // Import top-level structs/functions
+    use super::*;
+
+    // Drift ramps the temperature; a failure does not advance it
+    #[test]
+    fn test_drift() {
+        let mut sensor = SimSensor::new(Temperature::from_celsius(20));
+        sensor.set_drift(Temperature::from_celsius(1));
+        assert_eq!(sensor.read_temperature(), Ok(Temperature::from_celsius(20)));
+        sensor.fail_next_read();
+        assert_eq!(sensor.read_temperature(), Err(SimError));
+        assert_eq!(sensor.read_temperature(), Ok(Temperature::from_celsius(21)));
+        assert_eq!(sensor.read_temperature(), Ok(Temperature::from_celsius(22)));
+        assert_eq!(sensor.reads(), 4);
+    }
+
+    // The async trait gives the same readings
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_async() {
+        use crate::TemperatureSensorAsync;
+        use embassy_futures::block_on;
+
+        let mut sensor = SimSensor::new(Temperature::from_celsius(-5));
+        let temp = block_on(TemperatureSensorAsync::read_temperature(&mut sensor));
+        assert_eq!(temp, Ok(Temperature::from_celsius(-5)));
+    }
+}
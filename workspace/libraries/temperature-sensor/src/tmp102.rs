@@ -0,0 +1,79 @@
+//! [`TemperatureSensor`] for the tmp102-driver sensors
+//!
+//! Only continuous mode is covered: a one-shot reading in shutdown mode
+//! needs a delay, so wrap the driver in your own type for that.
+
+use embedded_hal::i2c::I2c;
+use tmp102_driver::{Continuous, Error, Sensor, Temperature, Variant};
+
+use crate::TemperatureSensor;
+
+impl<I2C, V> TemperatureSensor for Sensor<I2C, V, Continuous>
+where
+    I2C: I2c,
+    V: Variant,
+{
+    type Error = Error<I2C::Error>;
+
+    fn read_temperature(&mut self) -> Result<Temperature, Self::Error> {
+        Sensor::read_temperature(self)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<I2C, V> crate::TemperatureSensorAsync for tmp102_driver::SensorAsync<I2C, V, Continuous>
+where
+    I2C: embedded_hal_async::i2c::I2c,
+    V: Variant,
+{
+    type Error = Error<I2C::Error>;
+
+    async fn read_temperature(&mut self) -> Result<Temperature, Self::Error> {
+        tmp102_driver::SensorAsync::read_temperature(self).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    // Import top-level structs/functions
+    use super::*;
+
+    // Test-only imports
+    use tmp102_driver::sim::Tmp102Sim;
+    use tmp102_driver::{Address, TMP102};
+
+    // Driver readings come through the trait unchanged
+    #[test]
+    fn test_tmp102() {
+        let mut sim = Tmp102Sim::new(Address::Ground);
+        sim.set_temperature(Temperature::from_millicelsius(-10_250));
+        let mut tmp102 = TMP102::new(&mut sim, Address::Ground);
+        let temp = TemperatureSensor::read_temperature(&mut tmp102).unwrap();
+        assert_eq!(temp, Temperature::from_millicelsius(-10_250));
+    }
+
+    // Driver errors are passed through
+    #[test]
+    fn test_tmp102_error() {
+        let mut sim = Tmp102Sim::new(Address::Sda);
+        let mut tmp102 = TMP102::new(&mut sim, Address::Ground);
+        let result = TemperatureSensor::read_temperature(&mut tmp102);
+        assert!(matches!(result, Err(Error::NoAcknowledge(_))));
+    }
+
+    // Async driver implements the async trait
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_tmp102_async() {
+        use crate::TemperatureSensorAsync;
+        use embassy_futures::block_on;
+        use tmp102_driver::Tmp102Async;
+
+        let mut sim = Tmp102Sim::new(Address::Ground);
+        sim.set_temperature(Temperature::from_celsius(40));
+        let mut tmp102 = Tmp102Async::new(&mut sim, Address::Ground);
+        let temp = block_on(TemperatureSensorAsync::read_temperature(&mut tmp102)).unwrap();
+        assert_eq!(temp, Temperature::from_celsius(40));
+    }
+}
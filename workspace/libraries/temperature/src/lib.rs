@@ -0,0 +1,309 @@
+#![no_std]
+
+//! # Temperature
+//!
+//! The fixed-point temperature type shared by the sensor driver and the
+//! sensor-agnostic libraries, so code written against any sensor does not
+//! depend on a particular driver.
+//!
+//! Readings are stored as 1/16 °C (0.0625 °C) counts, the native resolution
+//! of the TMP102 family, so conversions and comparisons need no floating
+//! point. This matters on cores without an FPU such as the RP2040's
+//! Cortex-M0+.
+
+use core::fmt;
+
+/// Temperature with 0.0625 °C resolution
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Temperature(i16);
+
+impl Temperature {
+    /// Create from raw 1/16 °C counts
+    pub const fn from_counts(counts: i16) -> Self {
+        Self(counts)
+    }
+
+    /// Create from whole degrees Celsius
+    pub const fn from_celsius(celsius: i16) -> Self {
+        Self(celsius.saturating_mul(16))
+    }
+
+    /// Create from milli-degrees Celsius, rounded to the nearest count
+    pub const fn from_millicelsius(millicelsius: i32) -> Self {
+        // One count is 62.5 m°C, so work in half milli-degrees (125 per count)
+        let half_millis = millicelsius as i64 * 2;
+        let counts = if half_millis < 0 {
+            (half_millis - 62) / 125
+        } else {
+            (half_millis + 62) / 125
+        };
+        Self(saturate(counts))
+    }
+
+    /// Create from degrees Celsius, rounded to the nearest count
+    pub fn from_celsius_f32(celsius: f32) -> Self {
+        // Float to int casts saturate, so out-of-range values clamp
+        let scaled = celsius * 16.0;
+        let counts = if scaled < 0.0 {
+            scaled - 0.5
+        } else {
+            scaled + 0.5
+        };
+        Self(saturate(counts as i64))
+    }
+
+    /// Raw 1/16 °C counts
+    pub const fn counts(self) -> i16 {
+        self.0
+    }
+
+    /// Milli-degrees Celsius
+    ///
+    /// Odd counts fall on half a milli-degree and are rounded away from zero.
+    pub const fn as_millicelsius(self) -> i32 {
+        round_half(self.0 as i32 * 125)
+    }
+
+    /// Milli-degrees Fahrenheit (rounded away from zero on half steps)
+    pub const fn as_millifahrenheit(self) -> i32 {
+        // 1 count = 0.0625 °C = 0.1125 °F = 112.5 m°F
+        round_half(self.0 as i32 * 225) + 32_000
+    }
+
+    /// Milli-kelvin (rounded away from zero on half steps)
+    pub const fn as_millikelvin(self) -> i32 {
+        self.as_millicelsius() + 273_150
+    }
+
+    /// Degrees Celsius as a float
+    pub fn as_celsius_f32(self) -> f32 {
+        self.0 as f32 * 0.0625
+    }
+
+    /// Degrees Fahrenheit as a float
+    pub fn as_fahrenheit_f32(self) -> f32 {
+        self.as_celsius_f32() * 1.8 + 32.0
+    }
+
+    /// Kelvin as a float
+    pub fn as_kelvin_f32(self) -> f32 {
+        self.as_celsius_f32() + 273.15
+    }
+}
+
+/// Clamp a count to the range of `i16`
+const fn saturate(counts: i64) -> i16 {
+    if counts > i16::MAX as i64 {
+        i16::MAX
+    } else if counts < i16::MIN as i64 {
+        i16::MIN
+    } else {
+        counts as i16
+    }
+}
+
+/// Divide by two, rounding half steps away from zero
+const fn round_half(value: i32) -> i32 {
+    if value < 0 {
+        (value - 1) / 2
+    } else {
+        (value + 1) / 2
+    }
+}
+
+impl fmt::Display for Temperature {
+    /// Format as degrees Celsius without floating point
+    ///
+    /// Prints all four decimals by default (every count is exact at that
+    /// precision); a smaller precision such as `{:.2}` rounds to nearest.
+    /// Width, fill and alignment work as for numbers, e.g. `{:>8.2}`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precision = f.precision().unwrap_or(4).min(4);
+
+        // Work in units of 0.0001 °C: one count is exactly 625 units
+        let units = (self.0 as i32).unsigned_abs() * 625;
+        let divisor = 10u32.pow(4 - precision as u32);
+        let rounded = (units + divisor / 2) / divisor;
+        let scale = 10u32.pow(precision as u32);
+
+        // Digits without the sign, right to left (longest is "2048.0000")
+        let mut buf = [0u8; 9];
+        let mut pos = buf.len();
+        let mut fraction = rounded % scale;
+        for _ in 0..precision {
+            pos -= 1;
+            buf[pos] = b'0' + (fraction % 10) as u8;
+            fraction /= 10;
+        }
+        if precision > 0 {
+            pos -= 1;
+            buf[pos] = b'.';
+        }
+        let mut whole = rounded / scale;
+        loop {
+            pos -= 1;
+            buf[pos] = b'0' + (whole % 10) as u8;
+            whole /= 10;
+            if whole == 0 {
+                break;
+            }
+        }
+        let digits = core::str::from_utf8(&buf[pos..]).map_err(|_| fmt::Error)?;
+
+        // Do not print "-0.00" for values that round to zero
+        let is_nonnegative = self.0 >= 0 || rounded == 0;
+        f.pad_integral(is_nonnegative, "", digits)
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Temperature {
+    /// Log as degrees Celsius with four decimals, like `Display`
+    fn format(&self, f: defmt::Formatter) {
+        let units = (self.0 as i32).unsigned_abs() * 625;
+        let sign = if self.0 < 0 { "-" } else { "" };
+        defmt::write!(
+            f,
+            "{=str}{}.{=u32:04} C",
+            sign,
+            units / 10_000,
+            units % 10_000
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    // Import top-level structs/functions
+    use super::*;
+
+    // Explicitly link to std
+    extern crate std;
+    use std::format;
+
+    // Exact conversions to integer units
+    #[test]
+    fn test_integer_conversions() {
+        let t = Temperature::from_counts(400);
+        assert_eq!(t.as_millicelsius(), 25_000);
+        assert_eq!(t.as_millifahrenheit(), 77_000);
+        assert_eq!(t.as_millikelvin(), 298_150);
+
+        let t = Temperature::from_counts(-880);
+        assert_eq!(t.as_millicelsius(), -55_000);
+        assert_eq!(t.as_millifahrenheit(), -67_000);
+
+        // Half milli-degree steps round away from zero
+        assert_eq!(Temperature::from_counts(1).as_millicelsius(), 63);
+        assert_eq!(Temperature::from_counts(-1).as_millicelsius(), -63);
+        assert_eq!(Temperature::from_counts(2).as_millicelsius(), 125);
+    }
+
+    // Float conversions
+    #[test]
+    fn test_float_conversions() {
+        let t = Temperature::from_celsius(100);
+        assert_eq!(t.as_celsius_f32(), 100.0);
+        assert_eq!(t.as_fahrenheit_f32(), 212.0);
+        assert_eq!(t.as_kelvin_f32(), 373.15);
+        assert_eq!(Temperature::from_counts(-3).as_celsius_f32(), -0.1875);
+    }
+
+    // Construction rounds to the nearest count and saturates
+    #[test]
+    fn test_constructors() {
+        assert_eq!(Temperature::from_celsius(-55).counts(), -880);
+        assert_eq!(Temperature::from_millicelsius(62_500).counts(), 1000);
+        assert_eq!(Temperature::from_millicelsius(31).counts(), 0);
+        assert_eq!(Temperature::from_millicelsius(32).counts(), 1);
+        assert_eq!(Temperature::from_millicelsius(-32).counts(), -1);
+        assert_eq!(Temperature::from_celsius_f32(30.03).counts(), 480);
+        assert_eq!(Temperature::from_celsius_f32(-10.0625).counts(), -161);
+        assert_eq!(Temperature::from_celsius_f32(1.0e9).counts(), i16::MAX);
+        assert_eq!(Temperature::from_millicelsius(i32::MIN).counts(), i16::MIN);
+
+        // Milli-degree round trip is lossless for every count
+        for counts in -4096..=4095 {
+            let t = Temperature::from_counts(counts);
+            assert_eq!(Temperature::from_millicelsius(t.as_millicelsius()), t);
+        }
+    }
+
+    // Ordering follows the temperature
+    #[test]
+    fn test_ordering() {
+        let cold = Temperature::from_celsius(-10);
+        let warm = Temperature::from_celsius(25);
+        assert!(cold < warm);
+        assert_eq!(cold.max(warm), warm);
+    }
+
+    // Display without floating point
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", Temperature::from_counts(401)), "25.0625");
+        assert_eq!(format!("{}", Temperature::from_counts(-3)), "-0.1875");
+        assert_eq!(format!("{:.2}", Temperature::from_counts(401)), "25.06");
+        assert_eq!(format!("{:.2}", Temperature::from_counts(-3)), "-0.19");
+        assert_eq!(format!("{:.1}", Temperature::from_counts(-1)), "-0.1");
+        assert_eq!(format!("{:.0}", Temperature::from_counts(-1)), "0");
+        assert_eq!(format!("{:.0}", Temperature::from_counts(-888)), "-56");
+        assert_eq!(format!("{:.2}", Temperature::from_celsius(150)), "150.00");
+        assert_eq!(
+            format!("{}", Temperature::from_counts(i16::MIN)),
+            "-2048.0000"
+        );
+    }
+
+    // Display pads like a number
+    #[test]
+    fn test_display_padding() {
+        let t = Temperature::from_counts(401);
+        assert_eq!(format!("{:>8.2}", t), "   25.06");
+        assert_eq!(format!("{:<8.2}|", t), "25.06   |");
+        assert_eq!(format!("{:*^9.1}", t), "**25.1***");
+        assert_eq!(format!("{:+.2}", t), "+25.06");
+        assert_eq!(
+            format!("{:08.2}", Temperature::from_celsius(-3)),
+            "-0003.00"
+        );
+        assert_eq!(format!("{:>6.0}", Temperature::from_counts(-1)), "     0");
+    }
+
+    // Property tests
+    mod properties {
+
+        // Import top-level structs/functions
+        use super::*;
+
+        // Test-only imports
+        use proptest::prelude::*;
+
+        proptest! {
+            // Negative values keep their sign and magnitude in every unit
+            #[test]
+            fn prop_negative(counts in i16::MIN..0) {
+                let temp = Temperature::from_counts(counts);
+                let exact = counts as f64 * 62.5;
+                prop_assert!(temp.as_millicelsius() < 0);
+                prop_assert!((temp.as_millicelsius() as f64 - exact).abs() <= 0.5);
+                prop_assert_eq!(temp.as_celsius_f32(), counts as f32 / 16.0);
+                let text = format!("{}", temp);
+                prop_assert!(text.starts_with('-'));
+            }
+
+            // Milli-degrees round-trip for every count
+            #[test]
+            fn prop_millicelsius_round_trip(counts: i16) {
+                let temp = Temperature::from_counts(counts);
+                prop_assert_eq!(Temperature::from_millicelsius(temp.as_millicelsius()), temp);
+            }
+        }
+    }
+}
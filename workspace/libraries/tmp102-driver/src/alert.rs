@@ -0,0 +1,487 @@
+//! ALERT pin handling
+//!
+//! [`Alert`] wraps the GPIO wired to the sensor's ALERT output and turns
+//! its level into an [`AlertState`] using the configured polarity and
+//! thermostat mode:
+//!
+//! - Comparator mode: the pin is active from T ≥ T_HIGH until T < T_LOW,
+//!   so an active pin always means [`AlertState::OverTemp`]
+//! - Interrupt mode: the pin asserts once when T ≥ T_HIGH, stays asserted
+//!   until any register is read, then asserts once when T < T_LOW, and so
+//!   on. Call [`Alert::acknowledge`] outside the interrupt handler to clear
+//!   the pin and learn from the sensor which event it was.
+//!
+//! Any register read clears an interrupt-mode alert, so an event can be
+//! lost to an unrelated read (e.g. configuring the sensor after switching
+//! to interrupt mode). [`Alert::state`] only guesses the event from the
+//! last one acknowledged; [`Alert::acknowledge`] compares the temperature
+//! with the thresholds and resynchronizes the guess.
+//!
+//! For interrupt-driven code, enable the GPIO interrupt on
+//! [`Alert::assert_edge`] (e.g. `Interrupt::EdgeLow` for [`Edge::Falling`]
+//! on the RP2350), keep the `Alert` in a `Mutex<RefCell<Option<_>>>` shared
+//! with the `IO_IRQ_BANK0` handler, and use [`Alert::pin_mut`] there to
+//! check and clear the HAL's interrupt status.
+//!
+//! With several sensors on one open-drain ALERT line, put them in interrupt
+//! mode with active-low polarity and use [`smbus_alert_response`] to find
+//! out which one asserted it.
+//!
+//! With the `async` feature, `Alert::acknowledge_async` and
+//! `smbus_alert_response_async` do the same for the async driver.
+
+use embedded_hal::digital::InputPin;
+use embedded_hal::i2c::{ErrorKind, I2c};
+
+use crate::{AlertPolarity, Config, Error, PowerMode, Sensor, ThermostatMode, Variant};
+
+/// SMBus Alert Response Address
+pub const ALERT_RESPONSE_ADDRESS: u8 = 0x0C;
+
+/// Condition reported by the ALERT pin
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AlertState {
+    /// No alert
+    Normal,
+    /// Temperature reached T_HIGH
+    OverTemp,
+    /// Temperature fell below T_LOW (interrupt mode only)
+    UnderTemp,
+}
+
+/// GPIO edge on which the ALERT pin asserts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Edge {
+    Falling,
+    Rising,
+}
+
+/// Answer to an SMBus alert response read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AlertResponse {
+    /// 7-bit address of the sensor that asserted ALERT
+    pub address: u8,
+    /// Which threshold it crossed
+    pub state: AlertState,
+}
+
+/// ALERT pin companion for a sensor driver
+pub struct Alert<P> {
+    pin: P,
+    polarity: AlertPolarity,
+    mode: ThermostatMode,
+    next: AlertState,
+}
+
+impl<P> Alert<P>
+where
+    P: InputPin,
+{
+    /// Wrap the ALERT input using the sensor's configuration
+    ///
+    /// The configuration is not read from the sensor, so pass the one that
+    /// was written (or read back) and call [`Alert::set_config`] after any
+    /// change to polarity or thermostat mode.
+    pub fn new(pin: P, config: Config) -> Self {
+        Self {
+            pin,
+            polarity: config.alert_polarity(),
+            mode: config.thermostat_mode(),
+            next: AlertState::OverTemp,
+        }
+    }
+
+    /// Update polarity and thermostat mode after a configuration change
+    ///
+    /// Changing the thermostat mode restarts the interrupt-mode sequence
+    /// with an over-temperature event.
+    pub fn set_config(&mut self, config: Config) {
+        if config.thermostat_mode() != self.mode {
+            self.next = AlertState::OverTemp;
+        }
+        self.polarity = config.alert_polarity();
+        self.mode = config.thermostat_mode();
+    }
+
+    /// Whether the pin is at its active level
+    pub fn is_active(&mut self) -> Result<bool, P::Error> {
+        match self.polarity {
+            AlertPolarity::ActiveLow => self.pin.is_low(),
+            AlertPolarity::ActiveHigh => self.pin.is_high(),
+        }
+    }
+
+    /// Current alert condition, from the pin alone
+    ///
+    /// Does not touch the bus, so it is safe to call from an interrupt
+    /// handler. In interrupt mode the event is the opposite of the last one
+    /// acknowledged, which is wrong if an event was cleared by some other
+    /// register read; prefer the result of [`Alert::acknowledge`].
+    pub fn state(&mut self) -> Result<AlertState, P::Error> {
+        if !self.is_active()? {
+            return Ok(AlertState::Normal);
+        }
+        Ok(match self.mode {
+            ThermostatMode::Comparator => AlertState::OverTemp,
+            ThermostatMode::Interrupt => self.next,
+        })
+    }
+
+    /// Clear an alert and report the condition that caused it
+    ///
+    /// Returns [`AlertState::Normal`] without touching the bus if the pin
+    /// is inactive. Otherwise the condition comes from the sensor:
+    ///
+    /// - Comparator mode: the AL bit of the configuration register (the pin
+    ///   follows the temperature, so there is nothing to clear)
+    /// - Interrupt mode: the temperature read, which releases the pin,
+    ///   compared with T_HIGH and T_LOW. If it is back between the two, the
+    ///   expected event is reported. Either way the next expected event is
+    ///   the opposite of the one returned.
+    ///
+    /// If the pin cannot be read, the sensor is asked anyway.
+    pub fn acknowledge<I2C, V, MODE>(
+        &mut self,
+        sensor: &mut Sensor<I2C, V, MODE>,
+    ) -> Result<AlertState, Error<I2C::Error>>
+    where
+        I2C: I2c,
+        V: Variant,
+        MODE: PowerMode,
+    {
+        if let Ok(false) = self.is_active() {
+            return Ok(AlertState::Normal);
+        }
+        if self.mode == ThermostatMode::Comparator {
+            let config = sensor.read_config()?;
+            return Ok(self.comparator_state(config));
+        }
+
+        // Any read releases the pin; the temperature also tells the event
+        let temperature = sensor.fetch_temperature()?;
+        let event = if temperature >= sensor.read_high_threshold()? {
+            Some(AlertState::OverTemp)
+        } else if temperature < sensor.read_low_threshold()? {
+            Some(AlertState::UnderTemp)
+        } else {
+            None
+        };
+        Ok(self.record_event(event))
+    }
+
+    /// GPIO edge to trigger on when the alert asserts
+    pub fn assert_edge(&self) -> Edge {
+        match self.polarity {
+            AlertPolarity::ActiveLow => Edge::Falling,
+            AlertPolarity::ActiveHigh => Edge::Rising,
+        }
+    }
+
+    /// Access the pin, e.g. to clear its interrupt in a handler
+    pub fn pin_mut(&mut self) -> &mut P {
+        &mut self.pin
+    }
+
+    /// Release the pin
+    pub fn release(self) -> P {
+        self.pin
+    }
+
+    /// Thermostat mode the pin is interpreted in
+    pub fn thermostat_mode(&self) -> ThermostatMode {
+        self.mode
+    }
+
+    /// Condition shown by the AL bit of a configuration (comparator mode)
+    pub(crate) fn comparator_state(&self, config: Config) -> AlertState {
+        let level_high = config.alert();
+        if level_high == (self.polarity == AlertPolarity::ActiveHigh) {
+            AlertState::OverTemp
+        } else {
+            AlertState::Normal
+        }
+    }
+
+    /// Report an acknowledged interrupt-mode event (the expected one if the
+    /// sensor could not tell) and expect the opposite one next
+    pub(crate) fn record_event(&mut self, event: Option<AlertState>) -> AlertState {
+        let event = event.unwrap_or(self.next);
+        self.next = match event {
+            AlertState::OverTemp => AlertState::UnderTemp,
+            _ => AlertState::OverTemp,
+        };
+        event
+    }
+}
+
+#[cfg(feature = "async")]
+impl<P> Alert<P>
+where
+    P: InputPin + embedded_hal_async::digital::Wait,
+{
+    /// Wait until the pin is active, then report the alert condition
+    pub async fn wait(&mut self) -> Result<AlertState, P::Error> {
+        match self.polarity {
+            AlertPolarity::ActiveLow => self.pin.wait_for_low().await?,
+            AlertPolarity::ActiveHigh => self.pin.wait_for_high().await?,
+        }
+        self.state()
+    }
+}
+
+/// Ask which device on the bus is asserting ALERT (SMBus ARA)
+///
+/// Returns `Ok(None)` if no device answers. If several are asserting, the
+/// one with the lowest address answers and releases ALERT; call this again
+/// until it returns `None`. Requires interrupt mode and active-low
+/// polarity on the sensors (with active-high polarity the reported
+/// threshold is inverted).
+pub fn smbus_alert_response<I2C>(i2c: &mut I2C) -> Result<Option<AlertResponse>, Error<I2C::Error>>
+where
+    I2C: I2c,
+{
+    let mut rx_buf = [0u8; 1];
+    let result = i2c.read(ALERT_RESPONSE_ADDRESS, &mut rx_buf);
+    decode_alert_response(result, rx_buf[0])
+}
+
+/// Turn the result of an alert response read into the answering device
+///
+/// A missing acknowledge means no device is asserting ALERT.
+pub(crate) fn decode_alert_response<E>(
+    result: Result<(), E>,
+    byte: u8,
+) -> Result<Option<AlertResponse>, Error<E>>
+where
+    E: embedded_hal::i2c::Error,
+{
+    match result {
+        Ok(()) => {}
+        Err(e) if matches!(e.kind(), ErrorKind::NoAcknowledge(_)) => return Ok(None),
+        Err(e) => return Err(Error::from(e)),
+    }
+
+    // The LSB tells which threshold caused the alert: with POL=0 it is low
+    // for T ≥ T_HIGH (like the pin) and high for T < T_LOW
+    let state = if byte & 0x01 == 0 {
+        AlertState::OverTemp
+    } else {
+        AlertState::UnderTemp
+    };
+    Ok(Some(AlertResponse {
+        address: byte >> 1,
+        state,
+    }))
+}
+
+impl<I2C, V, MODE> Sensor<I2C, V, MODE>
+where
+    I2C: I2c,
+    V: Variant,
+    MODE: PowerMode,
+{
+    /// Ask which device on this sensor's bus is asserting ALERT
+    ///
+    /// See [`smbus_alert_response`].
+    pub fn smbus_alert_response(&mut self) -> Result<Option<AlertResponse>, Error<I2C::Error>> {
+        smbus_alert_response(&mut self.i2c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    // Import top-level structs/functions
+    use super::*;
+
+    // Test-only imports
+    use crate::sim::Tmp102Sim;
+    use crate::{Address, TMP102, Temperature};
+    use core::cell::RefCell;
+    use embedded_hal::digital::ErrorType;
+    use embedded_hal::i2c::NoAcknowledgeSource;
+    use embedded_hal_bus::i2c::RefCellDevice;
+    use i2c_mock::{I2cMock, Transaction};
+
+    // Input pin that mirrors the simulator's ALERT output
+    struct SimPin<'a>(&'a RefCell<Tmp102Sim>);
+
+    impl ErrorType for SimPin<'_> {
+        type Error = core::convert::Infallible;
+    }
+
+    impl InputPin for SimPin<'_> {
+        fn is_high(&mut self) -> Result<bool, Self::Error> {
+            Ok(self.0.borrow().alert_pin_high())
+        }
+
+        fn is_low(&mut self) -> Result<bool, Self::Error> {
+            Ok(!self.0.borrow().alert_pin_high())
+        }
+    }
+
+    // Set the simulated die temperature
+    fn set(bus: &RefCell<Tmp102Sim>, celsius: i16) {
+        bus.borrow_mut()
+            .set_temperature(Temperature::from_celsius(celsius));
+    }
+
+    // Comparator mode: active pin means over temperature
+    #[test]
+    fn test_comparator() {
+        let bus = RefCell::new(Tmp102Sim::default());
+        let mut tmp102 = TMP102::with_default_address(RefCellDevice::new(&bus));
+        tmp102
+            .set_alert_polarity(AlertPolarity::ActiveHigh)
+            .unwrap();
+        let config = tmp102.read_config().unwrap();
+        let mut alert = Alert::new(SimPin(&bus), config);
+        assert_eq!(alert.assert_edge(), Edge::Rising);
+
+        set(&bus, 25);
+        assert_eq!(alert.state().unwrap(), AlertState::Normal);
+        set(&bus, 80);
+        assert_eq!(alert.state().unwrap(), AlertState::OverTemp);
+
+        // Acknowledging reports AL and does not clear comparator mode
+        assert_eq!(
+            alert.acknowledge(&mut tmp102).unwrap(),
+            AlertState::OverTemp
+        );
+        assert_eq!(alert.state().unwrap(), AlertState::OverTemp);
+        set(&bus, 74);
+        assert_eq!(alert.state().unwrap(), AlertState::Normal);
+        assert_eq!(alert.acknowledge(&mut tmp102).unwrap(), AlertState::Normal);
+    }
+
+    // Interrupt mode: events alternate and are cleared by acknowledge
+    #[test]
+    fn test_interrupt() {
+        let bus = RefCell::new(Tmp102Sim::default());
+        let mut tmp102 = TMP102::with_default_address(RefCellDevice::new(&bus));
+        tmp102
+            .set_thermostat_mode(ThermostatMode::Interrupt)
+            .unwrap();
+        let config = tmp102.read_config().unwrap();
+        let mut alert = Alert::new(SimPin(&bus), config);
+        assert_eq!(alert.assert_edge(), Edge::Falling);
+
+        set(&bus, 80);
+        assert_eq!(alert.state().unwrap(), AlertState::OverTemp);
+        assert_eq!(alert.state().unwrap(), AlertState::OverTemp);
+        assert_eq!(
+            alert.acknowledge(&mut tmp102).unwrap(),
+            AlertState::OverTemp
+        );
+        assert_eq!(alert.state().unwrap(), AlertState::Normal);
+
+        // Acknowledging an inactive pin changes nothing
+        assert_eq!(alert.acknowledge(&mut tmp102).unwrap(), AlertState::Normal);
+
+        set(&bus, 70);
+        assert_eq!(alert.state().unwrap(), AlertState::UnderTemp);
+        assert_eq!(
+            alert.acknowledge(&mut tmp102).unwrap(),
+            AlertState::UnderTemp
+        );
+        assert_eq!(alert.state().unwrap(), AlertState::Normal);
+
+        set(&bus, 81);
+        assert_eq!(alert.state().unwrap(), AlertState::OverTemp);
+    }
+
+    // An event cleared by another register read does not invert the rest
+    #[test]
+    fn test_interrupt_resync() {
+        let bus = RefCell::new(Tmp102Sim::default());
+        let mut tmp102 = TMP102::with_default_address(RefCellDevice::new(&bus));
+        let config = tmp102
+            .read_config()
+            .unwrap()
+            .with_thermostat_mode(ThermostatMode::Interrupt);
+        tmp102.write_config(config).unwrap();
+        let mut alert = Alert::new(SimPin(&bus), config);
+
+        // Already hot: the over-temperature event is lost to a read
+        set(&bus, 90);
+        tmp102.read_config().unwrap();
+        assert_eq!(alert.state().unwrap(), AlertState::Normal);
+
+        // Cooling down: the pin alone guesses wrong, the sensor knows
+        set(&bus, 20);
+        assert_eq!(alert.state().unwrap(), AlertState::OverTemp);
+        assert_eq!(
+            alert.acknowledge(&mut tmp102).unwrap(),
+            AlertState::UnderTemp
+        );
+
+        // Back in step for the following events
+        set(&bus, 90);
+        assert_eq!(alert.state().unwrap(), AlertState::OverTemp);
+        assert_eq!(
+            alert.acknowledge(&mut tmp102).unwrap(),
+            AlertState::OverTemp
+        );
+        set(&bus, 20);
+        assert_eq!(alert.state().unwrap(), AlertState::UnderTemp);
+    }
+
+    // SMBus alert response identifies the sensor and the threshold
+    #[test]
+    fn test_smbus_alert_response() {
+        let bus = RefCell::new(Tmp102Sim::new(Address::Sda));
+        let mut tmp102 = TMP102::new(RefCellDevice::new(&bus), Address::Sda);
+        tmp102
+            .set_thermostat_mode(ThermostatMode::Interrupt)
+            .unwrap();
+
+        // Nobody is alerting
+        assert_eq!(tmp102.smbus_alert_response().unwrap(), None);
+
+        set(&bus, 90);
+        let response = tmp102.smbus_alert_response().unwrap().unwrap();
+        assert_eq!(response.address, 0x4A);
+        assert_eq!(response.state, AlertState::OverTemp);
+        assert!(!bus.borrow().alert_active());
+
+        set(&bus, 20);
+        let mut i2c = RefCellDevice::new(&bus);
+        let response = smbus_alert_response(&mut i2c).unwrap().unwrap();
+        assert_eq!(response.state, AlertState::UnderTemp);
+        assert_eq!(smbus_alert_response(&mut i2c).unwrap(), None);
+    }
+
+    // Alert response bytes decode as in the datasheet (POL=0: LSB low for
+    // T ≥ T_HIGH, high for T < T_LOW)
+    #[test]
+    fn test_smbus_alert_response_bytes() {
+        let mut i2c = I2cMock::new(&[
+            Transaction::read(ALERT_RESPONSE_ADDRESS, &[0x90]),
+            Transaction::read(ALERT_RESPONSE_ADDRESS, &[0x97]),
+            Transaction::read(ALERT_RESPONSE_ADDRESS, &[0x00])
+                .with_error(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address)),
+        ]);
+        assert_eq!(
+            smbus_alert_response(&mut i2c).unwrap(),
+            Some(AlertResponse {
+                address: 0x48,
+                state: AlertState::OverTemp,
+            })
+        );
+        assert_eq!(
+            smbus_alert_response(&mut i2c).unwrap(),
+            Some(AlertResponse {
+                address: 0x4B,
+                state: AlertState::UnderTemp,
+            })
+        );
+        assert_eq!(smbus_alert_response(&mut i2c).unwrap(), None);
+        i2c.done();
+    }
+}
@@ -0,0 +1,215 @@
+//! Several TMP102 sensors on one shared I2C bus
+//!
+//! Each [`TMP102`] owns its bus, so sharing one peripheral between sensors
+//! takes a bus-sharing wrapper from `embedded-hal-bus`. Each handle borrows
+//! the real bus and implements `I2c` itself:
+//!
+//! ```ignore
+//! use core::cell::RefCell;
+//! use embedded_hal_bus::i2c::RefCellDevice;
+//! use tmp102_driver::Tmp102Array;
+//!
+//! let bus = RefCell::new(i2c);
+//! let mut sensors = Tmp102Array::probe(core::array::from_fn(|_| RefCellDevice::new(&bus)));
+//! let readings = sensors.read_all();
+//! ```
+//!
+//! Use `CriticalSectionDevice` instead of `RefCellDevice` if the bus is also
+//! used from interrupt handlers.
+
+use embedded_hal::i2c::I2c;
+
+use crate::{Address, Error, TMP102, Temperature};
+
+/// Result of reading one sensor in a [`Tmp102Array`]
+pub type Reading<E> = Option<Result<Temperature, Error<E>>>;
+
+/// Up to four TMP102 sensors, one per ADD0 address
+pub struct Tmp102Array<I2C> {
+    sensors: [Option<TMP102<I2C>>; 4],
+}
+
+impl<I2C> Tmp102Array<I2C>
+where
+    I2C: I2c,
+{
+    /// Probe all four addresses and keep the sensors that respond
+    ///
+    /// `buses` holds one bus handle per address, in [`Address::ALL`] order.
+    /// A sensor counts as present if its configuration register reads back
+    /// valid; handles for missing sensors are dropped.
+    pub fn probe(buses: [I2C; 4]) -> Self {
+        let [ground, vdd, sda, scl] = buses;
+        Self {
+            sensors: [
+                Self::probe_one(ground, Address::Ground),
+                Self::probe_one(vdd, Address::Vdd),
+                Self::probe_one(sda, Address::Sda),
+                Self::probe_one(scl, Address::Scl),
+            ],
+        }
+    }
+
+    /// Whether a sensor answered at the given address
+    pub fn is_present(&self, address: Address) -> bool {
+        self.sensors[address.index()].is_some()
+    }
+
+    /// Addresses of all sensors that answered the probe
+    pub fn present(&self) -> impl Iterator<Item = Address> + '_ {
+        Address::ALL
+            .into_iter()
+            .filter(|address| self.is_present(*address))
+    }
+
+    /// Number of sensors that answered the probe
+    pub fn count(&self) -> usize {
+        self.sensors.iter().flatten().count()
+    }
+
+    /// Access one sensor, e.g. to configure thresholds
+    pub fn sensor_mut(&mut self, address: Address) -> Option<&mut TMP102<I2C>> {
+        self.sensors[address.index()].as_mut()
+    }
+
+    /// Read every present sensor in one call
+    ///
+    /// The result is in [`Address::ALL`] order: `None` for a sensor that is
+    /// not present, otherwise that sensor's reading or error. One failing
+    /// sensor does not stop the others from being read.
+    pub fn read_all(&mut self) -> [Reading<I2C::Error>; 4] {
+        let [ground, vdd, sda, scl] = &mut self.sensors;
+        [
+            ground.as_mut().map(TMP102::read_temperature),
+            vdd.as_mut().map(TMP102::read_temperature),
+            sda.as_mut().map(TMP102::read_temperature),
+            scl.as_mut().map(TMP102::read_temperature),
+        ]
+    }
+
+    /// Create a driver if a sensor answers at the address
+    fn probe_one(i2c: I2C, address: Address) -> Option<TMP102<I2C>> {
+        let mut sensor = TMP102::new(i2c, address);
+        sensor.read_config().ok().map(|_| sensor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    // Import top-level structs/functions
+    use super::*;
+
+    // Reuse the single-device stub for each sensor on the bus
+    use crate::tests::{DummyError, I2cStub};
+
+    // Test-only imports
+    use core::cell::RefCell;
+    use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource, Operation};
+    use embedded_hal_bus::i2c::RefCellDevice;
+
+    // Bus with a stub sensor at some of the four addresses
+    struct MultiStub {
+        devices: [Option<I2cStub>; 4],
+    }
+
+    impl MultiStub {
+        // Look up the device at an address, or NACK like a real bus
+        fn device(&mut self, address: u8) -> Result<&mut I2cStub, DummyError> {
+            let nack = DummyError(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address));
+            Address::ALL
+                .iter()
+                .position(|a| a.as_u8() == address)
+                .and_then(|i| self.devices[i].as_mut())
+                .ok_or(nack)
+        }
+    }
+
+    impl embedded_hal::i2c::ErrorType for MultiStub {
+        type Error = DummyError;
+    }
+
+    // Forward each transfer to the addressed stub
+    impl I2c for MultiStub {
+        fn write(&mut self, address: u8, write: &[u8]) -> Result<(), Self::Error> {
+            self.device(address)?.write(address, write)
+        }
+
+        fn write_read(
+            &mut self,
+            address: u8,
+            write: &[u8],
+            read: &mut [u8],
+        ) -> Result<(), Self::Error> {
+            self.device(address)?.write_read(address, write, read)
+        }
+
+        fn transaction(
+            &mut self,
+            address: u8,
+            operations: &mut [Operation<'_>],
+        ) -> Result<(), Self::Error> {
+            self.device(address)?.transaction(address, operations)
+        }
+    }
+
+    // Create a stub sensor reporting a temperature
+    fn sensor(temp_c: f32) -> Option<I2cStub> {
+        let mut stub = I2cStub::new();
+        stub.set_temperature(temp_c);
+        Some(stub)
+    }
+
+    // Only responding addresses are reported present
+    #[test]
+    fn test_probe() {
+        let bus = RefCell::new(MultiStub {
+            devices: [sensor(20.0), None, sensor(-5.0), None],
+        });
+        let sensors = Tmp102Array::probe(core::array::from_fn(|_| RefCellDevice::new(&bus)));
+
+        assert_eq!(sensors.count(), 2);
+        assert!(sensors.is_present(Address::Ground));
+        assert!(!sensors.is_present(Address::Vdd));
+        let mut present = sensors.present();
+        assert_eq!(present.next().map(Address::as_u8), Some(0x48));
+        assert_eq!(present.next().map(Address::as_u8), Some(0x4A));
+        assert!(present.next().is_none());
+    }
+
+    // Read all four addresses, each reporting its own result
+    #[test]
+    fn test_read_all() {
+        let bus = RefCell::new(MultiStub {
+            devices: [sensor(20.0), sensor(31.5), None, sensor(-5.0)],
+        });
+        let mut sensors = Tmp102Array::probe(core::array::from_fn(|_| RefCellDevice::new(&bus)));
+
+        // Sensor on SCL fails after the probe
+        bus.borrow_mut().devices[3].as_mut().unwrap().fail_with = Some(ErrorKind::Bus);
+
+        let [ground, vdd, sda, scl] = sensors.read_all();
+        assert_eq!(ground.unwrap().unwrap(), Temperature::from_celsius(20));
+        assert_eq!(vdd.unwrap().unwrap().as_millicelsius(), 31_500);
+        assert!(sda.is_none());
+        assert!(matches!(scl, Some(Err(Error::Bus(_)))));
+    }
+
+    // Individual sensors can still be configured
+    #[test]
+    fn test_sensor_mut() {
+        let bus = RefCell::new(MultiStub {
+            devices: [None, sensor(0.0), None, None],
+        });
+        let mut sensors = Tmp102Array::probe(core::array::from_fn(|_| RefCellDevice::new(&bus)));
+
+        assert!(sensors.sensor_mut(Address::Ground).is_none());
+        let vdd = sensors.sensor_mut(Address::Vdd).unwrap();
+        vdd.set_high_threshold(Temperature::from_celsius(40))
+            .unwrap();
+        assert_eq!(
+            bus.borrow().devices[1].as_ref().unwrap().registers[3],
+            [0x28, 0x00]
+        );
+    }
+}
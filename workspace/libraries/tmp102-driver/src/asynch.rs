@@ -0,0 +1,694 @@
+//! Async TMP102 driver built on `embedded-hal-async`
+//!
+//! Mirrors the blocking [`crate::Sensor`] API so it can be used from async
+//! executors such as Embassy without stalling other tasks.
+
+use core::marker::PhantomData;
+
+use embedded_hal::digital::InputPin;
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::i2c::I2c;
+
+use crate::alert::decode_alert_response;
+use crate::clock::Cache;
+use crate::variant::{self, Lm75, Tmp75, Tmp102, Tmp112};
+use crate::{
+    ALERT_RESPONSE_ADDRESS, Address, Alert, AlertPolarity, AlertResponse, AlertState,
+    CONVERSION_POLL_MS, CONVERSION_POLLS, CachedReading, Clock, Config, Continuous, ConversionRate,
+    ConversionReady, Error, ExtendedMode, FaultQueue, OneShot, PowerMode, Register, RegisterDump,
+    Shutdown, Temperature, ThermostatMode, Variant,
+};
+
+/// Async TMP102 driver
+pub type Tmp102Async<I2C, MODE = Continuous> = SensorAsync<I2C, Tmp102, MODE>;
+
+/// Async TMP112 driver
+pub type Tmp112Async<I2C, MODE = Continuous> = SensorAsync<I2C, Tmp112, MODE>;
+
+/// Async TMP75 driver
+pub type Tmp75Async<I2C, MODE = Continuous> = SensorAsync<I2C, Tmp75, MODE>;
+
+/// Async LM75 driver
+pub type Lm75Async<I2C, MODE = Continuous> = SensorAsync<I2C, Lm75, MODE>;
+
+/// Async temperature sensor driver for any supported [`Variant`]
+///
+/// Uses the same [`Continuous`] and [`Shutdown`] typestates as the blocking
+/// driver.
+pub struct SensorAsync<I2C, V, MODE = Continuous> {
+    i2c: I2C,
+    address: Address,
+    extended_mode: bool,
+    conversion_rate: ConversionRate,
+    cache: Option<Cache>,
+    variant: PhantomData<V>,
+    mode: PhantomData<MODE>,
+}
+
+impl<I2C, V> SensorAsync<I2C, V, Continuous>
+where
+    I2C: I2c,
+    V: Variant,
+{
+    /// Create a new async driver instance
+    ///
+    /// The sensor is assumed to be in its power-on state (continuous
+    /// conversions, normal data format).
+    pub fn new(i2c: I2C, address: Address) -> Self {
+        Self {
+            i2c,
+            address,
+            extended_mode: false,
+            conversion_rate: ConversionRate::Hz4,
+            cache: None,
+            variant: PhantomData,
+            mode: PhantomData,
+        }
+    }
+
+    /// Create new instance with default address (Ground)
+    pub fn with_default_address(i2c: I2C) -> Self {
+        Self::new(i2c, Address::Ground)
+    }
+
+    /// Read the current temperature
+    pub async fn read_temperature(&mut self) -> Result<Temperature, Error<I2C::Error>> {
+        self.fetch_temperature().await
+    }
+
+    /// Read the temperature only if a new conversion is due
+    ///
+    /// See [`crate::Sensor::read_temperature_cached`].
+    pub async fn read_temperature_cached<C: Clock>(
+        &mut self,
+        clock: &C,
+    ) -> Result<CachedReading, Error<I2C::Error>> {
+        let now_us = clock.now_us();
+        let period_us = self.conversion_period_us();
+        if let Some(temperature) = self.cache.and_then(|c| c.get(now_us, period_us)) {
+            return Ok(CachedReading {
+                temperature,
+                fresh: false,
+            });
+        }
+        let temperature = self.fetch_temperature().await?;
+        self.cache = Some(Cache::new(temperature, now_us));
+        Ok(CachedReading {
+            temperature,
+            fresh: true,
+        })
+    }
+
+    /// Read the current temperature in degrees Celsius
+    pub async fn read_temperature_c(&mut self) -> Result<f32, Error<I2C::Error>> {
+        self.read_temperature()
+            .await
+            .map(Temperature::as_celsius_f32)
+    }
+
+    /// Shut the sensor down
+    pub async fn into_shutdown(
+        mut self,
+    ) -> Result<SensorAsync<I2C, V, Shutdown>, Error<I2C::Error>> {
+        self.write_shutdown(true).await?;
+        Ok(self.into_mode())
+    }
+}
+
+impl<I2C, V> SensorAsync<I2C, V, Shutdown>
+where
+    I2C: I2c,
+    V: Variant,
+{
+    /// Return to continuous conversions
+    pub async fn into_continuous(
+        mut self,
+    ) -> Result<SensorAsync<I2C, V, Continuous>, Error<I2C::Error>> {
+        self.write_shutdown(false).await?;
+        Ok(self.into_mode())
+    }
+}
+
+impl<I2C, V> SensorAsync<I2C, V, Shutdown>
+where
+    I2C: I2c,
+    V: OneShot,
+{
+    /// Start a single conversion; the sensor shuts down again afterwards
+    pub async fn start_conversion(&mut self) -> Result<(), Error<I2C::Error>> {
+        self.modify_config(|c| c.with_one_shot(true)).await
+    }
+
+    /// Take a single reading
+    ///
+    /// Other tasks keep running while this waits for the conversion.
+    pub async fn read_temperature_one_shot<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<Temperature, Error<I2C::Error>> {
+        self.start_conversion().await?;
+        if !V::READY_FLAG {
+            delay.delay_ms(V::CONVERSION_TIME_MAX_MS).await;
+            return self.fetch_temperature().await;
+        }
+        delay.delay_ms(V::CONVERSION_TIME_MS).await;
+        for _ in 0..CONVERSION_POLLS {
+            if self.read_config().await?.one_shot() {
+                return self.fetch_temperature().await;
+            }
+            delay.delay_ms(CONVERSION_POLL_MS).await;
+        }
+        Err(Error::ConversionTimeout)
+    }
+}
+
+impl<I2C, V> SensorAsync<I2C, V, Shutdown>
+where
+    I2C: I2c,
+    V: ConversionReady,
+{
+    /// Check whether a one-shot conversion has finished
+    pub async fn poll_conversion(&mut self) -> Result<Option<Temperature>, Error<I2C::Error>> {
+        if self.read_config().await?.one_shot() {
+            self.fetch_temperature().await.map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<I2C, V, MODE> SensorAsync<I2C, V, MODE>
+where
+    I2C: I2c,
+    V: ExtendedMode,
+    MODE: PowerMode,
+{
+    /// Enable or disable extended (13-bit) mode
+    pub async fn set_extended_mode(&mut self, enable: bool) -> Result<(), Error<I2C::Error>> {
+        self.modify_config(|c| c.with_extended_mode(enable)).await
+    }
+}
+
+impl<I2C, V, MODE> SensorAsync<I2C, V, MODE>
+where
+    I2C: I2c,
+    V: Variant,
+    MODE: PowerMode,
+{
+    /// Read the configuration register
+    pub async fn read_config(&mut self) -> Result<Config, Error<I2C::Error>> {
+        let bits = self.read_register(Register::Config).await?;
+        let config = V::decode_config(bits).ok_or(Error::InvalidConfig)?;
+        self.extended_mode = config.extended_mode();
+        self.conversion_rate = config.conversion_rate();
+        Ok(config)
+    }
+
+    /// Write the configuration register (SD is forced to match `MODE`)
+    pub async fn write_config(&mut self, config: Config) -> Result<(), Error<I2C::Error>> {
+        self.write_config_register(config.with_shutdown(MODE::SHUTDOWN))
+            .await
+    }
+
+    /// Whether the driver is decoding readings in extended (13-bit) mode
+    pub fn extended_mode(&self) -> bool {
+        self.extended_mode
+    }
+
+    /// Maximum error of this sensor variant (datasheet)
+    pub fn accuracy(&self) -> Temperature {
+        V::ACCURACY
+    }
+
+    /// Time between conversions in continuous mode
+    pub fn conversion_period_us(&self) -> u32 {
+        if V::HAS_CONVERSION_RATE {
+            self.conversion_rate.period_us()
+        } else {
+            V::CONVERSION_PERIOD_US
+        }
+    }
+
+    /// Set the lower alert threshold (T_LOW)
+    pub async fn set_low_threshold(&mut self, temp: Temperature) -> Result<(), Error<I2C::Error>> {
+        let raw = variant::encode::<V>(temp, self.extended_mode);
+        self.write_register(Register::TLow, raw).await
+    }
+
+    /// Set the upper alert threshold (T_HIGH)
+    pub async fn set_high_threshold(&mut self, temp: Temperature) -> Result<(), Error<I2C::Error>> {
+        let raw = variant::encode::<V>(temp, self.extended_mode);
+        self.write_register(Register::THigh, raw).await
+    }
+
+    /// Read the lower alert threshold (T_LOW)
+    pub async fn read_low_threshold(&mut self) -> Result<Temperature, Error<I2C::Error>> {
+        let raw = self.read_register(Register::TLow).await?;
+        Ok(variant::decode::<V>(raw, self.extended_mode))
+    }
+
+    /// Read the upper alert threshold (T_HIGH)
+    pub async fn read_high_threshold(&mut self) -> Result<Temperature, Error<I2C::Error>> {
+        let raw = self.read_register(Register::THigh).await?;
+        Ok(variant::decode::<V>(raw, self.extended_mode))
+    }
+
+    /// Select comparator or interrupt behavior for the ALERT pin
+    pub async fn set_thermostat_mode(
+        &mut self,
+        mode: ThermostatMode,
+    ) -> Result<(), Error<I2C::Error>> {
+        self.modify_config(|c| c.with_thermostat_mode(mode)).await
+    }
+
+    /// Set the number of consecutive faults needed to trigger the alert
+    pub async fn set_fault_queue(
+        &mut self,
+        fault_queue: FaultQueue,
+    ) -> Result<(), Error<I2C::Error>> {
+        self.modify_config(|c| c.with_fault_queue(fault_queue))
+            .await
+    }
+
+    /// Set the active level of the ALERT pin
+    pub async fn set_alert_polarity(
+        &mut self,
+        polarity: AlertPolarity,
+    ) -> Result<(), Error<I2C::Error>> {
+        self.modify_config(|c| c.with_alert_polarity(polarity))
+            .await
+    }
+
+    /// Read a register as raw bits (8-bit registers in the upper byte)
+    pub async fn read_register(&mut self, register: Register) -> Result<u16, Error<I2C::Error>> {
+        let address = self.address.as_u8();
+        let pointer = [register.as_u8()];
+        if register == Register::Config && V::CONFIG_BYTES == 1 {
+            let mut rx_buf = [0u8; 1];
+            self.i2c
+                .write_read(address, &pointer, &mut rx_buf)
+                .await
+                .map_err(Error::from)?;
+            return Ok(u16::from(rx_buf[0]) << 8);
+        }
+        let mut rx_buf = [0u8; 2];
+        self.i2c
+            .write_read(address, &pointer, &mut rx_buf)
+            .await
+            .map_err(Error::from)?;
+        Ok(u16::from_be_bytes(rx_buf))
+    }
+
+    /// Write raw bits to a register (MSB first, not checked against `MODE`)
+    pub async fn write_register(
+        &mut self,
+        register: Register,
+        value: u16,
+    ) -> Result<(), Error<I2C::Error>> {
+        let address = self.address.as_u8();
+        let [msb, lsb] = value.to_be_bytes();
+        let result = if register == Register::Config && V::CONFIG_BYTES == 1 {
+            self.i2c.write(address, &[register.as_u8(), msb]).await
+        } else {
+            self.i2c.write(address, &[register.as_u8(), msb, lsb]).await
+        };
+        result.map_err(Error::from)?;
+
+        // Keep decoding readings in the format just selected
+        if register == Register::Config {
+            let config = Config::from_bits(value);
+            self.extended_mode = V::HAS_EXTENDED_MODE && config.extended_mode();
+            self.conversion_rate = config.conversion_rate();
+            self.cache = None;
+        }
+        Ok(())
+    }
+
+    /// Ask which device on this sensor's bus is asserting ALERT
+    ///
+    /// See [`smbus_alert_response_async`].
+    pub async fn smbus_alert_response(
+        &mut self,
+    ) -> Result<Option<AlertResponse>, Error<I2C::Error>> {
+        smbus_alert_response_async(&mut self.i2c).await
+    }
+
+    /// Read all four registers, e.g. for a debug console
+    pub async fn dump_registers(&mut self) -> Result<RegisterDump, Error<I2C::Error>> {
+        Ok(RegisterDump {
+            temperature: self.read_register(Register::Temperature).await?,
+            config: self.read_register(Register::Config).await?,
+            t_low: self.read_register(Register::TLow).await?,
+            t_high: self.read_register(Register::THigh).await?,
+        })
+    }
+
+    /// Release the I2C bus
+    pub fn destroy(self) -> I2C {
+        self.i2c
+    }
+
+    /// Read the temperature register
+    async fn fetch_temperature(&mut self) -> Result<Temperature, Error<I2C::Error>> {
+        let raw = self.read_register(Register::Temperature).await?;
+        Ok(variant::decode::<V>(raw, self.extended_mode))
+    }
+
+    /// Set or clear SD, bypassing the `MODE` check in `write_config`
+    async fn write_shutdown(&mut self, shutdown: bool) -> Result<(), Error<I2C::Error>> {
+        let config = self.read_config().await?.with_one_shot(false);
+        self.write_config_register(config.with_shutdown(shutdown))
+            .await
+    }
+
+    /// Hand the bus over to a driver in another mode
+    fn into_mode<NEW>(self) -> SensorAsync<I2C, V, NEW> {
+        SensorAsync {
+            i2c: self.i2c,
+            address: self.address,
+            extended_mode: self.extended_mode,
+            conversion_rate: self.conversion_rate,
+            cache: None,
+            variant: PhantomData,
+            mode: PhantomData,
+        }
+    }
+
+    /// Read-modify-write the configuration register (clearing OS)
+    async fn modify_config<F>(&mut self, f: F) -> Result<(), Error<I2C::Error>>
+    where
+        F: FnOnce(Config) -> Config,
+    {
+        let config = self.read_config().await?.with_one_shot(false);
+        self.write_config(f(config)).await
+    }
+
+    /// Encode and write the configuration register as given
+    async fn write_config_register(&mut self, config: Config) -> Result<(), Error<I2C::Error>> {
+        self.write_register(Register::Config, V::encode_config(config))
+            .await
+    }
+}
+
+impl<P> Alert<P>
+where
+    P: InputPin,
+{
+    /// Clear an alert and report the condition that caused it
+    ///
+    /// Same as [`Alert::acknowledge`], for the async driver.
+    pub async fn acknowledge_async<I2C, V, MODE>(
+        &mut self,
+        sensor: &mut SensorAsync<I2C, V, MODE>,
+    ) -> Result<AlertState, Error<I2C::Error>>
+    where
+        I2C: I2c,
+        V: Variant,
+        MODE: PowerMode,
+    {
+        if let Ok(false) = self.is_active() {
+            return Ok(AlertState::Normal);
+        }
+        if self.thermostat_mode() == ThermostatMode::Comparator {
+            let config = sensor.read_config().await?;
+            return Ok(self.comparator_state(config));
+        }
+
+        // Any read releases the pin; the temperature also tells the event
+        let temperature = sensor.fetch_temperature().await?;
+        let event = if temperature >= sensor.read_high_threshold().await? {
+            Some(AlertState::OverTemp)
+        } else if temperature < sensor.read_low_threshold().await? {
+            Some(AlertState::UnderTemp)
+        } else {
+            None
+        };
+        Ok(self.record_event(event))
+    }
+}
+
+/// Ask which device on the bus is asserting ALERT (SMBus ARA)
+///
+/// Same as [`crate::smbus_alert_response`], for an async bus.
+pub async fn smbus_alert_response_async<I2C>(
+    i2c: &mut I2C,
+) -> Result<Option<AlertResponse>, Error<I2C::Error>>
+where
+    I2C: I2c,
+{
+    let mut rx_buf = [0u8; 1];
+    let result = i2c.read(ALERT_RESPONSE_ADDRESS, &mut rx_buf).await;
+    decode_alert_response(result, rx_buf[0])
+}
+
+#[cfg(test)]
+mod tests {
+
+    // Import top-level structs/functions
+    use super::*;
+
+    // Reuse the blocking stubs (they also implement the async traits)
+    use crate::tests::{DelayStub, I2cStub};
+
+    // Simple executor: poll the future to completion on the current thread
+    use embassy_futures::block_on;
+    use embedded_hal::digital::ErrorType;
+    use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+    use i2c_mock::{I2cMock, Transaction};
+
+    // ALERT input stub (active low, like the sensor's power-on default)
+    struct AlertPinStub {
+        asserted: bool,
+    }
+
+    impl ErrorType for AlertPinStub {
+        type Error = core::convert::Infallible;
+    }
+
+    impl InputPin for AlertPinStub {
+        fn is_high(&mut self) -> Result<bool, Self::Error> {
+            Ok(!self.asserted)
+        }
+
+        fn is_low(&mut self) -> Result<bool, Self::Error> {
+            Ok(self.asserted)
+        }
+    }
+
+    // Create a new driver and make sure the device address is set
+    #[test]
+    fn test_new_driver() {
+        let driver = Tmp102Async::new(I2cStub::new(), Address::Vdd);
+        assert_eq!(driver.address.as_u8(), 0x49);
+    }
+
+    // Read the temperature in both formats
+    #[test]
+    fn test_temperature_read() {
+        let mut driver = Tmp102Async::with_default_address(I2cStub::new());
+        driver.i2c.set_temperature(25.0);
+        assert_eq!(block_on(driver.read_temperature_c()).unwrap(), 25.0);
+
+        block_on(driver.set_extended_mode(true)).unwrap();
+        driver.i2c.registers[0] = [0x4B, 0x01];
+        let temp = block_on(driver.read_temperature()).unwrap();
+        assert_eq!(temp, Temperature::from_celsius(150));
+    }
+
+    // Configuration and thresholds match the blocking driver byte-for-byte
+    #[test]
+    fn test_config_and_thresholds() {
+        let mut driver = Tmp102Async::with_default_address(I2cStub::new());
+        block_on(async {
+            driver
+                .set_thermostat_mode(ThermostatMode::Interrupt)
+                .await
+                .unwrap();
+            driver.set_fault_queue(FaultQueue::Four).await.unwrap();
+            driver
+                .set_alert_polarity(AlertPolarity::ActiveHigh)
+                .await
+                .unwrap();
+            driver
+                .set_low_threshold(Temperature::from_counts(-161))
+                .await
+                .unwrap();
+            driver
+                .set_high_threshold(Temperature::from_celsius(30))
+                .await
+                .unwrap();
+        });
+        assert_eq!(driver.i2c.registers[1], [0x76, 0xA0]);
+        assert_eq!(driver.i2c.registers[2], [0xF5, 0xF0]);
+        assert_eq!(driver.i2c.registers[3], [0x1E, 0x00]);
+        let low = block_on(driver.read_low_threshold()).unwrap();
+        let high = block_on(driver.read_high_threshold()).unwrap();
+        assert_eq!(low, Temperature::from_counts(-161));
+        assert_eq!(high, Temperature::from_celsius(30));
+    }
+
+    // One-shot conversion, both polled and awaited
+    #[test]
+    fn test_one_shot() {
+        let driver = Tmp102Async::with_default_address(I2cStub::new());
+        let mut driver = block_on(driver.into_shutdown()).unwrap();
+        driver.i2c.set_temperature(-4.5);
+        driver.i2c.converting = true;
+
+        block_on(driver.start_conversion()).unwrap();
+        assert_eq!(driver.i2c.registers[1], [0xE1, 0xA0]);
+        assert_eq!(block_on(driver.poll_conversion()).unwrap(), None);
+        driver.i2c.converting = false;
+        let expected = Temperature::from_millicelsius(-4_500);
+        assert_eq!(block_on(driver.poll_conversion()).unwrap(), Some(expected));
+
+        let mut delay = DelayStub::default();
+        let temp = block_on(driver.read_temperature_one_shot(&mut delay)).unwrap();
+        assert_eq!(temp, expected);
+        assert_eq!(delay.total_ns, 26_000_000);
+    }
+
+    // One-shot conversion times out if OS never sets
+    #[test]
+    fn test_one_shot_timeout() {
+        let driver = Tmp102Async::with_default_address(I2cStub::new());
+        let mut driver = block_on(driver.into_shutdown()).unwrap();
+        driver.i2c.converting = true;
+        let mut delay = DelayStub::default();
+
+        let result = block_on(driver.read_temperature_one_shot(&mut delay));
+        assert!(matches!(result, Err(Error::ConversionTimeout)));
+    }
+
+    // Bus traffic matches the blocking driver
+    #[test]
+    fn test_transactions() {
+        let mut driver = Tmp102Async::with_default_address(I2cMock::new(&[
+            Transaction::write_read(0x48, &[0x01], &[0x60, 0xA0]),
+            Transaction::write(0x48, &[0x01, 0x60, 0xB0]),
+            Transaction::write_read(0x48, &[0x00], &[0x4B, 0x01]),
+        ]));
+
+        block_on(async {
+            driver.set_extended_mode(true).await.unwrap();
+            let temp = driver.read_temperature().await.unwrap();
+            assert_eq!(temp, Temperature::from_celsius(150));
+        });
+        driver.i2c.done();
+    }
+
+    // Mode transitions only change the SD bit
+    #[test]
+    fn test_shutdown() {
+        let driver = Tmp102Async::with_default_address(I2cStub::new());
+        let driver = block_on(driver.into_shutdown()).unwrap();
+        assert_eq!(driver.i2c.registers[1], [0x61, 0xA0]);
+        let driver = block_on(driver.into_continuous()).unwrap();
+        assert_eq!(driver.i2c.registers[1], [0x60, 0xA0]);
+    }
+
+    // Raw access and releasing the bus
+    #[test]
+    fn test_register_access() {
+        let mut driver = Tmp102Async::with_default_address(I2cStub::new());
+        let dump = block_on(driver.dump_registers()).unwrap();
+        assert_eq!(dump.config, 0x60A0);
+        assert_eq!(dump.t_high, 0x5000);
+
+        block_on(driver.write_register(Register::THigh, 0x2800)).unwrap();
+        let i2c = driver.destroy();
+        assert_eq!(i2c.registers[3], [0x28, 0x00]);
+    }
+
+    // Cached reads skip the bus until a new conversion is due
+    #[test]
+    fn test_read_cached() {
+        let now = core::cell::Cell::new(0u64);
+        let clock = || now.get();
+        let mut driver = Tmp102Async::with_default_address(I2cStub::new());
+        assert!(
+            block_on(driver.read_temperature_cached(&clock))
+                .unwrap()
+                .fresh
+        );
+        now.set(200_000);
+        assert!(
+            !block_on(driver.read_temperature_cached(&clock))
+                .unwrap()
+                .fresh
+        );
+        assert_eq!(driver.i2c.call_count, 1);
+        now.set(250_000);
+        assert!(
+            block_on(driver.read_temperature_cached(&clock))
+                .unwrap()
+                .fresh
+        );
+    }
+
+    // Comparator mode reads AL; interrupt mode reads the temperature and
+    // thresholds, which releases the pin
+    #[test]
+    fn test_acknowledge() {
+        let comparator = Config::from_bits(0x60A0);
+        let interrupt = comparator.with_thermostat_mode(ThermostatMode::Interrupt);
+        let mut driver = Tmp102Async::with_default_address(I2cMock::new(&[
+            // Comparator mode, AL low (active): over temperature
+            Transaction::write_read(0x48, &[0x01], &[0x60, 0x80]),
+            // Interrupt mode, 90 °C against T_HIGH = 80 °C
+            Transaction::write_read(0x48, &[0x00], &[0x5A, 0x00]),
+            Transaction::write_read(0x48, &[0x03], &[0x50, 0x00]),
+            // Interrupt mode, 20 °C: below T_LOW = 75 °C
+            Transaction::write_read(0x48, &[0x00], &[0x14, 0x00]),
+            Transaction::write_read(0x48, &[0x03], &[0x50, 0x00]),
+            Transaction::write_read(0x48, &[0x02], &[0x4B, 0x00]),
+        ]));
+
+        let mut alert = Alert::new(AlertPinStub { asserted: true }, comparator);
+        let state = block_on(alert.acknowledge_async(&mut driver)).unwrap();
+        assert_eq!(state, AlertState::OverTemp);
+
+        alert.set_config(interrupt);
+        let state = block_on(alert.acknowledge_async(&mut driver)).unwrap();
+        assert_eq!(state, AlertState::OverTemp);
+        let state = block_on(alert.acknowledge_async(&mut driver)).unwrap();
+        assert_eq!(state, AlertState::UnderTemp);
+
+        // An inactive pin does not touch the bus
+        alert.pin_mut().asserted = false;
+        let state = block_on(alert.acknowledge_async(&mut driver)).unwrap();
+        assert_eq!(state, AlertState::Normal);
+        driver.i2c.done();
+    }
+
+    // Alert response bytes decode like the blocking version
+    #[test]
+    fn test_smbus_alert_response() {
+        let mut driver = Tmp102Async::with_default_address(I2cMock::new(&[
+            Transaction::read(ALERT_RESPONSE_ADDRESS, &[0x90]),
+            Transaction::read(ALERT_RESPONSE_ADDRESS, &[0x97]),
+            Transaction::read(ALERT_RESPONSE_ADDRESS, &[0x00])
+                .with_error(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address)),
+        ]));
+        assert_eq!(
+            block_on(driver.smbus_alert_response()).unwrap(),
+            Some(AlertResponse {
+                address: 0x48,
+                state: AlertState::OverTemp,
+            })
+        );
+        let mut i2c = driver.destroy();
+        assert_eq!(
+            block_on(smbus_alert_response_async(&mut i2c)).unwrap(),
+            Some(AlertResponse {
+                address: 0x4B,
+                state: AlertState::UnderTemp,
+            })
+        );
+        assert_eq!(
+            block_on(smbus_alert_response_async(&mut i2c)).unwrap(),
+            None
+        );
+        i2c.done();
+    }
+}
@@ -0,0 +1,53 @@
+//! Time source for cached reads
+//!
+//! The sensor only updates its temperature register once per conversion
+//! period (250 ms at the TMP102's default 4 Hz), so reading it more often
+//! returns the same value and keeps the bus busy for nothing. Given a
+//! [`Clock`], the `read_temperature_cached()` driver functions only go to
+//! the bus once a new conversion is due.
+//!
+//! [`Clock`](crate::Clock) comes from the `monotonic` crate and is re-exported here, so
+//! the same timer closure can also drive a debounced button:
+//!
+//! ```ignore
+//! let clock = || timer.get_counter().ticks();
+//! let reading = tmp102.read_temperature_cached(&clock)?;
+//! ```
+
+use crate::Temperature;
+
+/// Result of a cached read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CachedReading {
+    /// Latest known temperature
+    pub temperature: Temperature,
+    /// Whether this call read the sensor (false: returned from the cache)
+    pub fresh: bool,
+}
+
+/// Last reading and when it was taken
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Cache {
+    temperature: Temperature,
+    timestamp_us: u64,
+}
+
+impl Cache {
+    /// Remember a reading taken at `now_us`
+    pub(crate) fn new(temperature: Temperature, now_us: u64) -> Self {
+        Self {
+            temperature,
+            timestamp_us: now_us,
+        }
+    }
+
+    /// Cached temperature, if no new conversion is due yet
+    ///
+    /// A clock that went backwards counts as due.
+    pub(crate) fn get(&self, now_us: u64, period_us: u32) -> Option<Temperature> {
+        let age = now_us.checked_sub(self.timestamp_us)?;
+        (age < u64::from(period_us)).then_some(self.temperature)
+    }
+}
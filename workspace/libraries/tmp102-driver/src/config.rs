@@ -0,0 +1,415 @@
+//! Typed access to the TMP102 configuration register
+//!
+//! The configuration register is 16 bits wide and laid out as follows:
+//!
+//! | Bit | 15 | 14 | 13 | 12 | 11 | 10  | 9  | 8  | 7   | 6   | 5  | 4  | 3..0 |
+//! |-----|----|----|----|----|----|-----|----|----|-----|-----|----|----|------|
+//! |     | OS | R1 | R0 | F1 | F0 | POL | TM | SD | CR1 | CR0 | AL | EM | 0    |
+//!
+//! R1/R0 (resolution) and AL (alert status) are read-only.
+
+// Bit positions and masks
+const OS: u16 = 1 << 15;
+const RESOLUTION: u16 = 0b11 << 13;
+const FAULT_QUEUE_SHIFT: u16 = 11;
+const POL: u16 = 1 << 10;
+const TM: u16 = 1 << 9;
+const SD: u16 = 1 << 8;
+const CONVERSION_RATE_SHIFT: u16 = 6;
+const AL: u16 = 1 << 5;
+const EM: u16 = 1 << 4;
+const RESERVED: u16 = 0b1111;
+
+/// Number of temperature conversions per second (CR1:CR0)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConversionRate {
+    Hz0_25 = 0b00,
+    Hz1 = 0b01,
+    Hz4 = 0b10, // Default
+    Hz8 = 0b11,
+}
+
+impl ConversionRate {
+    fn from_bits(bits: u16) -> Self {
+        match bits & 0b11 {
+            0b00 => Self::Hz0_25,
+            0b01 => Self::Hz1,
+            0b10 => Self::Hz4,
+            _ => Self::Hz8,
+        }
+    }
+
+    /// Time between conversions in microseconds
+    pub fn period_us(self) -> u32 {
+        match self {
+            Self::Hz0_25 => 4_000_000,
+            Self::Hz1 => 1_000_000,
+            Self::Hz4 => 250_000,
+            Self::Hz8 => 125_000,
+        }
+    }
+}
+
+/// Consecutive faults required before the alert triggers (F1:F0)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FaultQueue {
+    One = 0b00, // Default
+    Two = 0b01,
+    Four = 0b10,
+    Six = 0b11,
+}
+
+impl FaultQueue {
+    fn from_bits(bits: u16) -> Self {
+        match bits & 0b11 {
+            0b00 => Self::One,
+            0b01 => Self::Two,
+            0b10 => Self::Four,
+            _ => Self::Six,
+        }
+    }
+}
+
+/// Active level of the ALERT pin and AL bit (POL)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AlertPolarity {
+    ActiveLow, // Default
+    ActiveHigh,
+}
+
+/// Thermostat behavior of the alert output (TM)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ThermostatMode {
+    Comparator, // Default
+    Interrupt,
+}
+
+/// Contents of the configuration register
+///
+/// Start from [`Config::default()`] (the power-on state) and chain the
+/// `with_*` setters to build the value passed to `TMP102::write_config`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Config {
+    one_shot: bool,
+    fault_queue: FaultQueue,
+    alert_polarity: AlertPolarity,
+    thermostat_mode: ThermostatMode,
+    shutdown: bool,
+    conversion_rate: ConversionRate,
+    alert: bool,
+    extended_mode: bool,
+}
+
+impl Default for Config {
+    /// Power-on configuration (0x60A0)
+    fn default() -> Self {
+        Self {
+            one_shot: false,
+            fault_queue: FaultQueue::One,
+            alert_polarity: AlertPolarity::ActiveLow,
+            thermostat_mode: ThermostatMode::Comparator,
+            shutdown: false,
+            conversion_rate: ConversionRate::Hz4,
+            alert: true,
+            extended_mode: false,
+        }
+    }
+}
+
+impl Config {
+    /// Decode a raw 16-bit register value
+    pub fn from_bits(bits: u16) -> Self {
+        Self {
+            one_shot: bits & OS != 0,
+            fault_queue: FaultQueue::from_bits(bits >> FAULT_QUEUE_SHIFT),
+            alert_polarity: if bits & POL != 0 {
+                AlertPolarity::ActiveHigh
+            } else {
+                AlertPolarity::ActiveLow
+            },
+            thermostat_mode: if bits & TM != 0 {
+                ThermostatMode::Interrupt
+            } else {
+                ThermostatMode::Comparator
+            },
+            shutdown: bits & SD != 0,
+            conversion_rate: ConversionRate::from_bits(bits >> CONVERSION_RATE_SHIFT),
+            alert: bits & AL != 0,
+            extended_mode: bits & EM != 0,
+        }
+    }
+
+    /// Decode a raw register value read back from the sensor
+    ///
+    /// Returns `None` if the read-only resolution bits are not `11` or the
+    /// reserved low bits are set. A floating bus (0xFFFF) or a different
+    /// device at the address fails this check.
+    pub fn try_from_bits(bits: u16) -> Option<Self> {
+        if bits & RESOLUTION == RESOLUTION && bits & RESERVED == 0 {
+            Some(Self::from_bits(bits))
+        } else {
+            None
+        }
+    }
+
+    /// Encode as a raw 16-bit register value
+    ///
+    /// The read-only resolution bits are always set to `11` (12-bit), which
+    /// matches what the sensor reports back.
+    pub fn to_bits(self) -> u16 {
+        let mut bits = RESOLUTION;
+        bits |= (self.fault_queue as u16) << FAULT_QUEUE_SHIFT;
+        bits |= (self.conversion_rate as u16) << CONVERSION_RATE_SHIFT;
+        if self.one_shot {
+            bits |= OS;
+        }
+        if self.alert_polarity == AlertPolarity::ActiveHigh {
+            bits |= POL;
+        }
+        if self.thermostat_mode == ThermostatMode::Interrupt {
+            bits |= TM;
+        }
+        if self.shutdown {
+            bits |= SD;
+        }
+        if self.alert {
+            bits |= AL;
+        }
+        if self.extended_mode {
+            bits |= EM;
+        }
+        bits
+    }
+
+    /// One-shot/conversion-ready bit (OS)
+    pub fn one_shot(&self) -> bool {
+        self.one_shot
+    }
+
+    /// Fault queue depth (F1:F0)
+    pub fn fault_queue(&self) -> FaultQueue {
+        self.fault_queue
+    }
+
+    /// Alert polarity (POL)
+    pub fn alert_polarity(&self) -> AlertPolarity {
+        self.alert_polarity
+    }
+
+    /// Thermostat mode (TM)
+    pub fn thermostat_mode(&self) -> ThermostatMode {
+        self.thermostat_mode
+    }
+
+    /// Shutdown mode (SD)
+    pub fn shutdown(&self) -> bool {
+        self.shutdown
+    }
+
+    /// Conversion rate (CR1:CR0)
+    pub fn conversion_rate(&self) -> ConversionRate {
+        self.conversion_rate
+    }
+
+    /// Alert status bit (AL, read-only)
+    pub fn alert(&self) -> bool {
+        self.alert
+    }
+
+    /// Extended 13-bit mode (EM)
+    pub fn extended_mode(&self) -> bool {
+        self.extended_mode
+    }
+
+    /// Set the one-shot bit (OS)
+    pub fn with_one_shot(mut self, one_shot: bool) -> Self {
+        self.one_shot = one_shot;
+        self
+    }
+
+    /// Set the fault queue depth (F1:F0)
+    pub fn with_fault_queue(mut self, fault_queue: FaultQueue) -> Self {
+        self.fault_queue = fault_queue;
+        self
+    }
+
+    /// Set the alert polarity (POL)
+    pub fn with_alert_polarity(mut self, alert_polarity: AlertPolarity) -> Self {
+        self.alert_polarity = alert_polarity;
+        self
+    }
+
+    /// Set the thermostat mode (TM)
+    pub fn with_thermostat_mode(mut self, thermostat_mode: ThermostatMode) -> Self {
+        self.thermostat_mode = thermostat_mode;
+        self
+    }
+
+    /// Enable or disable shutdown mode (SD)
+    pub fn with_shutdown(mut self, shutdown: bool) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
+    /// Set the conversion rate (CR1:CR0)
+    pub fn with_conversion_rate(mut self, conversion_rate: ConversionRate) -> Self {
+        self.conversion_rate = conversion_rate;
+        self
+    }
+
+    /// Enable or disable extended 13-bit mode (EM)
+    pub fn with_extended_mode(mut self, extended_mode: bool) -> Self {
+        self.extended_mode = extended_mode;
+        self
+    }
+
+    /// Set the read-only alert bit (AL), as the sensor would report it
+    pub(crate) fn with_alert(mut self, alert: bool) -> Self {
+        self.alert = alert;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    // Import top-level structs/functions
+    use super::*;
+
+    // Power-on value decodes to the default configuration
+    #[test]
+    fn test_default_bits() {
+        assert_eq!(Config::default().to_bits(), 0x60A0);
+        assert_eq!(Config::from_bits(0x60A0), Config::default());
+    }
+
+    // Each field lands in the correct bits
+    #[test]
+    fn test_field_encoding() {
+        let base = Config::default().to_bits();
+        assert_eq!(
+            Config::default().with_one_shot(true).to_bits(),
+            base | 0x8000
+        );
+        assert_eq!(
+            Config::default().with_shutdown(true).to_bits(),
+            base | 0x0100
+        );
+        assert_eq!(
+            Config::default().with_extended_mode(true).to_bits(),
+            base | 0x0010
+        );
+        assert_eq!(
+            Config::default()
+                .with_thermostat_mode(ThermostatMode::Interrupt)
+                .to_bits(),
+            base | 0x0200
+        );
+        assert_eq!(
+            Config::default()
+                .with_alert_polarity(AlertPolarity::ActiveHigh)
+                .to_bits(),
+            base | 0x0400
+        );
+        assert_eq!(
+            Config::default()
+                .with_fault_queue(FaultQueue::Six)
+                .to_bits(),
+            base | 0x1800
+        );
+        assert_eq!(
+            Config::default()
+                .with_conversion_rate(ConversionRate::Hz0_25)
+                .to_bits(),
+            base & !0x00C0
+        );
+    }
+
+    // Read-only bits are checked on readback
+    #[test]
+    fn test_try_from_bits() {
+        assert_eq!(Config::try_from_bits(0x60A0), Some(Config::default()));
+        assert_eq!(Config::try_from_bits(0xFFFF), None);
+        assert_eq!(Config::try_from_bits(0x0000), None);
+        assert_eq!(Config::try_from_bits(0x20A0), None);
+    }
+
+    // Every writable combination survives an encode/decode round trip
+    #[test]
+    fn test_round_trip() {
+        let rates = [
+            ConversionRate::Hz0_25,
+            ConversionRate::Hz1,
+            ConversionRate::Hz4,
+            ConversionRate::Hz8,
+        ];
+        let queues = [
+            FaultQueue::One,
+            FaultQueue::Two,
+            FaultQueue::Four,
+            FaultQueue::Six,
+        ];
+        for rate in rates {
+            for queue in queues {
+                for flag in [false, true] {
+                    let config = Config::default()
+                        .with_conversion_rate(rate)
+                        .with_fault_queue(queue)
+                        .with_shutdown(flag)
+                        .with_extended_mode(!flag);
+                    assert_eq!(Config::from_bits(config.to_bits()), config);
+                }
+            }
+        }
+    }
+
+    // Property tests over all register values
+    mod properties {
+
+        // Import top-level structs/functions
+        use super::*;
+
+        // Test-only imports
+        use proptest::prelude::*;
+
+        proptest! {
+            // Decoding keeps every meaningful bit
+            #[test]
+            fn prop_bits_round_trip(bits: u16) {
+                let expected = (bits | RESOLUTION) & !RESERVED;
+                prop_assert_eq!(Config::from_bits(bits).to_bits(), expected);
+            }
+
+            // Readback accepts exactly the values with valid read-only bits
+            #[test]
+            fn prop_try_from_bits(bits: u16) {
+                let valid = bits & RESOLUTION == RESOLUTION && bits & RESERVED == 0;
+                prop_assert_eq!(Config::try_from_bits(bits).is_some(), valid);
+                if let Some(config) = Config::try_from_bits(bits) {
+                    prop_assert_eq!(config.to_bits(), bits);
+                }
+            }
+
+            // Each setter changes only its own field
+            #[test]
+            fn prop_setters(bits: u16, shutdown: bool, extended: bool) {
+                let config = Config::from_bits(bits);
+                let changed = config.with_shutdown(shutdown).with_extended_mode(extended);
+                let diff = config.to_bits() ^ changed.to_bits();
+                prop_assert_eq!(diff & !(SD | EM), 0);
+                prop_assert_eq!(changed.shutdown(), shutdown);
+                prop_assert_eq!(changed.extended_mode(), extended);
+            }
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//! Driver error type
+
+use core::fmt;
+
+use embedded_hal::i2c::{self, ErrorKind};
+
+/// Custom error for our crate
+#[derive(Debug)]
+pub enum Error<E> {
+    /// I2C communication error not covered by a more specific variant
+    Communication(E),
+    /// Sensor did not acknowledge (missing, unpowered or wrong address)
+    NoAcknowledge(E),
+    /// Another controller won arbitration on a multi-controller bus
+    ArbitrationLoss(E),
+    /// Misplaced START or STOP condition on the bus
+    Bus(E),
+    /// One-shot conversion did not complete in time
+    ConversionTimeout,
+    /// Configuration register read back with impossible contents
+    InvalidConfig,
+}
+
+impl<E> Error<E> {
+    /// Underlying bus error, if this came from the I2C peripheral
+    pub fn bus_error(&self) -> Option<&E> {
+        match self {
+            Error::Communication(e)
+            | Error::NoAcknowledge(e)
+            | Error::ArbitrationLoss(e)
+            | Error::Bus(e) => Some(e),
+            Error::ConversionTimeout | Error::InvalidConfig => None,
+        }
+    }
+}
+
+impl<E> From<E> for Error<E>
+where
+    E: i2c::Error,
+{
+    /// Sort a HAL error into a variant based on its `ErrorKind`
+    fn from(e: E) -> Self {
+        match e.kind() {
+            ErrorKind::NoAcknowledge(_) => Error::NoAcknowledge(e),
+            ErrorKind::ArbitrationLoss => Error::ArbitrationLoss(e),
+            ErrorKind::Bus => Error::Bus(e),
+            _ => Error::Communication(e),
+        }
+    }
+}
+
+impl<E> fmt::Display for Error<E>
+where
+    E: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Communication(e) => write!(f, "I2C communication error ({:?})", e),
+            Error::NoAcknowledge(_) => {
+                write!(f, "no response from sensor (check wiring and address)")
+            }
+            Error::ArbitrationLoss(_) => write!(f, "lost I2C bus arbitration"),
+            Error::Bus(_) => write!(f, "I2C bus error (misplaced START or STOP)"),
+            Error::ConversionTimeout => write!(f, "one-shot conversion timed out"),
+            Error::InvalidConfig => write!(f, "invalid configuration register contents"),
+        }
+    }
+}
+
+impl<E> core::error::Error for Error<E> where E: fmt::Debug {}
+
+#[cfg(feature = "defmt")]
+impl<E> defmt::Format for Error<E> {
+    /// Log the error kind (the HAL error itself may not support defmt)
+    fn format(&self, f: defmt::Formatter) {
+        match self {
+            Error::Communication(_) => defmt::write!(f, "Communication"),
+            Error::NoAcknowledge(_) => defmt::write!(f, "NoAcknowledge"),
+            Error::ArbitrationLoss(_) => defmt::write!(f, "ArbitrationLoss"),
+            Error::Bus(_) => defmt::write!(f, "Bus"),
+            Error::ConversionTimeout => defmt::write!(f, "ConversionTimeout"),
+            Error::InvalidConfig => defmt::write!(f, "InvalidConfig"),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<E> serde::Serialize for Error<E> {
+    /// Serialize the error kind as a unit variant
+    ///
+    /// The HAL error is dropped like in the `defmt` output, so there is no
+    /// matching `Deserialize`.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (index, name) = match self {
+            Error::Communication(_) => (0, "Communication"),
+            Error::NoAcknowledge(_) => (1, "NoAcknowledge"),
+            Error::ArbitrationLoss(_) => (2, "ArbitrationLoss"),
+            Error::Bus(_) => (3, "Bus"),
+            Error::ConversionTimeout => (4, "ConversionTimeout"),
+            Error::InvalidConfig => (5, "InvalidConfig"),
+        };
+        serializer.serialize_unit_variant("Error", index, name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    // Import top-level structs/functions
+    use super::*;
+
+    // Explicitly link to std
+    extern crate std;
+    use std::format;
+
+    // Test-only imports
+    use embedded_hal::i2c::NoAcknowledgeSource;
+
+    // Minimal HAL error carrying a kind
+    #[derive(Debug)]
+    struct KindError(ErrorKind);
+
+    impl i2c::Error for KindError {
+        fn kind(&self) -> ErrorKind {
+            self.0
+        }
+    }
+
+    // Error kinds map onto variants
+    #[test]
+    fn test_from_error_kind() {
+        let nack = ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address);
+        assert!(matches!(
+            Error::from(KindError(nack)),
+            Error::NoAcknowledge(_)
+        ));
+        assert!(matches!(
+            Error::from(KindError(ErrorKind::ArbitrationLoss)),
+            Error::ArbitrationLoss(_)
+        ));
+        assert!(matches!(
+            Error::from(KindError(ErrorKind::Bus)),
+            Error::Bus(_)
+        ));
+        assert!(matches!(
+            Error::from(KindError(ErrorKind::Overrun)),
+            Error::Communication(_)
+        ));
+    }
+
+    // Display gives actionable messages
+    #[test]
+    fn test_display() {
+        let nack = ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address);
+        let e = Error::from(KindError(nack));
+        assert_eq!(
+            format!("{}", e),
+            "no response from sensor (check wiring and address)"
+        );
+        let e: Error<KindError> = Error::ConversionTimeout;
+        assert_eq!(format!("{}", e), "one-shot conversion timed out");
+        assert!(e.bus_error().is_none());
+    }
+}
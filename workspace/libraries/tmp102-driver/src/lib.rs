@@ -2,19 +2,69 @@
 
 //! # TMP102 Demo Driver
 //!
-//! A simple demo driver for the TMP102 temperature sensor
+//! A simple demo driver for the TMP102 temperature sensor and its relatives
+//! (TMP112, TMP75 and LM75), see [`variant`]
 
+use core::marker::PhantomData;
+
+use embedded_hal::delay::DelayNs;
 use embedded_hal::i2c::I2c;
 
-/// Custom error for our crate
-#[derive(Debug)]
-pub enum Error<E> {
-    /// I2C communication error
-    Communication(E),
-}
+// Driver error type
+mod error;
+pub use error::Error;
+
+// Configuration register types
+mod config;
+pub use config::{AlertPolarity, Config, ConversionRate, FaultQueue, ThermostatMode};
+
+// Power mode typestates
+mod mode;
+pub use mode::{Continuous, PowerMode, Shutdown};
+
+// Time source for cached reads
+mod clock;
+use clock::Cache;
+pub use clock::CachedReading;
+pub use monotonic::Clock;
+
+// Register map and raw access
+mod register;
+pub use register::{Register, RegisterDump};
+
+// Supported sensor variants
+pub mod variant;
+pub use variant::{ConversionReady, ExtendedMode, OneShot, Variant};
+
+// Fixed-point temperature type, shared with sensor-agnostic code
+pub use temperature::Temperature;
+
+// Async driver (enable with the `async` feature)
+#[cfg(feature = "async")]
+mod asynch;
+#[cfg(feature = "async")]
+pub use asynch::{
+    Lm75Async, SensorAsync, Tmp75Async, Tmp102Async, Tmp112Async, smbus_alert_response_async,
+};
+
+// ALERT pin and SMBus alert response
+mod alert;
+pub use alert::{
+    ALERT_RESPONSE_ADDRESS, Alert, AlertResponse, AlertState, Edge, smbus_alert_response,
+};
+
+// Several sensors sharing one bus
+mod array;
+pub use array::{Reading, Tmp102Array};
+
+// Register-accurate simulator for host tests (enable with the `sim` feature)
+#[cfg(any(test, feature = "sim"))]
+pub mod sim;
 
 /// Possible device addresses based on ADD0 pin connection
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Address {
     Ground = 0x48, // Default
     Vdd = 0x49,
@@ -23,37 +73,72 @@ pub enum Address {
 }
 
 impl Address {
+    /// All four addresses, in order
+    pub const ALL: [Address; 4] = [Address::Ground, Address::Vdd, Address::Sda, Address::Scl];
+
     /// Get the I2C address in u8 format
     pub fn as_u8(self) -> u8 {
         self as u8
     }
+
+    /// Position of this address in [`Address::ALL`]
+    fn index(self) -> usize {
+        (self.as_u8() - Address::Ground.as_u8()) as usize
+    }
 }
 
-/// List internal registers in a struct
-#[allow(dead_code)]
-struct Register;
+/// Time between OS bit polls once the typical conversion time has passed
+const CONVERSION_POLL_MS: u32 = 2;
 
-#[allow(dead_code)]
-impl Register {
-    const TEMPERATURE: u8 = 0x00;
-    const CONFIG: u8 = 0x01;
-    const T_LOW: u8 = 0x02;
-    const T_HIGH: u8 = 0x03;
-}
+/// Number of OS bit polls before giving up on a one-shot conversion
+const CONVERSION_POLLS: u32 = 10;
+
+/// TMP102 driver
+pub type TMP102<I2C, MODE = Continuous> = Sensor<I2C, variant::Tmp102, MODE>;
+
+/// TMP112 driver
+pub type TMP112<I2C, MODE = Continuous> = Sensor<I2C, variant::Tmp112, MODE>;
 
-/// TMP102 temperature sensor driver
-pub struct TMP102<I2C> {
+/// TMP75 driver
+pub type TMP75<I2C, MODE = Continuous> = Sensor<I2C, variant::Tmp75, MODE>;
+
+/// LM75 driver
+pub type LM75<I2C, MODE = Continuous> = Sensor<I2C, variant::Lm75, MODE>;
+
+/// Temperature sensor driver for any supported [`Variant`]
+///
+/// Usually named through an alias such as [`TMP102`] or [`LM75`]. `MODE` is
+/// the sensor's power mode, [`Continuous`] or [`Shutdown`]. See
+/// [`PowerMode`] for how modes are switched.
+pub struct Sensor<I2C, V, MODE = Continuous> {
     i2c: I2C,
     address: Address,
+    extended_mode: bool,
+    conversion_rate: ConversionRate,
+    cache: Option<Cache>,
+    variant: PhantomData<V>,
+    mode: PhantomData<MODE>,
 }
 
-impl<I2C> TMP102<I2C>
+impl<I2C, V> Sensor<I2C, V, Continuous>
 where
     I2C: I2c,
+    V: Variant,
 {
-    /// Create a new TMP102 driver instance
+    /// Create a new driver instance
+    ///
+    /// The sensor is assumed to be in its power-on state (continuous
+    /// conversions, normal data format).
     pub fn new(i2c: I2C, address: Address) -> Self {
-        Self { i2c, address }
+        Self {
+            i2c,
+            address,
+            extended_mode: false,
+            conversion_rate: ConversionRate::Hz4,
+            cache: None,
+            variant: PhantomData,
+            mode: PhantomData,
+        }
     }
 
     /// Create new instance with default address (Ground)
@@ -61,25 +146,334 @@ where
         Self::new(i2c, Address::Ground)
     }
 
+    /// Read the current temperature (blocking)
+    pub fn read_temperature(&mut self) -> Result<Temperature, Error<I2C::Error>> {
+        self.fetch_temperature()
+    }
+
     /// Read the current temperature in degrees Celsius (blocking)
     pub fn read_temperature_c(&mut self) -> Result<f32, Error<I2C::Error>> {
+        self.read_temperature().map(Temperature::as_celsius_f32)
+    }
+
+    /// Read the temperature only if a new conversion is due
+    ///
+    /// Returns the last reading without touching the bus until one
+    /// conversion period has passed since it was taken. Configuration
+    /// writes clear the cache.
+    pub fn read_temperature_cached<C: Clock>(
+        &mut self,
+        clock: &C,
+    ) -> Result<CachedReading, Error<I2C::Error>> {
+        let now_us = clock.now_us();
+        let period_us = self.conversion_period_us();
+        if let Some(temperature) = self.cache.and_then(|c| c.get(now_us, period_us)) {
+            return Ok(CachedReading {
+                temperature,
+                fresh: false,
+            });
+        }
+        let temperature = self.fetch_temperature()?;
+        self.cache = Some(Cache::new(temperature, now_us));
+        Ok(CachedReading {
+            temperature,
+            fresh: true,
+        })
+    }
+
+    /// Shut the sensor down
+    ///
+    /// In shutdown the sensor stops converting and draws very little current
+    /// (about 0.5 µA on the TMP102). Variants with [`OneShot`] support can
+    /// still take readings on request.
+    pub fn into_shutdown(mut self) -> Result<Sensor<I2C, V, Shutdown>, Error<I2C::Error>> {
+        self.write_shutdown(true)?;
+        Ok(self.into_mode())
+    }
+}
+
+impl<I2C, V> Sensor<I2C, V, Shutdown>
+where
+    I2C: I2c,
+    V: Variant,
+{
+    /// Return to continuous conversions
+    pub fn into_continuous(mut self) -> Result<Sensor<I2C, V, Continuous>, Error<I2C::Error>> {
+        self.write_shutdown(false)?;
+        Ok(self.into_mode())
+    }
+}
+
+impl<I2C, V> Sensor<I2C, V, Shutdown>
+where
+    I2C: I2c,
+    V: OneShot,
+{
+    /// Start a single conversion; the sensor shuts down again afterwards
+    ///
+    /// Call [`Sensor::poll_conversion`] until it returns a reading, or wait
+    /// [`OneShot::CONVERSION_TIME_MAX_MS`] on variants without a ready flag.
+    pub fn start_conversion(&mut self) -> Result<(), Error<I2C::Error>> {
+        self.modify_config(|c| c.with_one_shot(true))
+    }
+
+    /// Take a single reading (blocking)
+    ///
+    /// Waits for the typical conversion time, then polls the OS bit until
+    /// the result is ready or [`Error::ConversionTimeout`] is returned.
+    /// Variants without a ready flag wait the maximum conversion time.
+    pub fn read_temperature_one_shot<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<Temperature, Error<I2C::Error>> {
+        self.start_conversion()?;
+        if !V::READY_FLAG {
+            delay.delay_ms(V::CONVERSION_TIME_MAX_MS);
+            return self.fetch_temperature();
+        }
+        delay.delay_ms(V::CONVERSION_TIME_MS);
+        for _ in 0..CONVERSION_POLLS {
+            if self.read_config()?.one_shot() {
+                return self.fetch_temperature();
+            }
+            delay.delay_ms(CONVERSION_POLL_MS);
+        }
+        Err(Error::ConversionTimeout)
+    }
+}
+
+impl<I2C, V> Sensor<I2C, V, Shutdown>
+where
+    I2C: I2c,
+    V: ConversionReady,
+{
+    /// Check whether a one-shot conversion has finished (non-blocking)
+    ///
+    /// Returns `Ok(None)` while the conversion is still in progress and the
+    /// new temperature once the OS bit reads back as 1.
+    pub fn poll_conversion(&mut self) -> Result<Option<Temperature>, Error<I2C::Error>> {
+        if self.read_config()?.one_shot() {
+            self.fetch_temperature().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<I2C, V, MODE> Sensor<I2C, V, MODE>
+where
+    I2C: I2c,
+    V: ExtendedMode,
+    MODE: PowerMode,
+{
+    /// Enable or disable extended (13-bit) mode
+    ///
+    /// Extended mode raises the upper limit from 128 °C to 150 °C.
+    pub fn set_extended_mode(&mut self, enable: bool) -> Result<(), Error<I2C::Error>> {
+        self.modify_config(|c| c.with_extended_mode(enable))
+    }
+}
+
+impl<I2C, V, MODE> Sensor<I2C, V, MODE>
+where
+    I2C: I2c,
+    V: Variant,
+    MODE: PowerMode,
+{
+    /// Read the configuration register
+    ///
+    /// Returns [`Error::InvalidConfig`] if the read-only bits are wrong, which
+    /// usually means some other kind of device answered.
+    pub fn read_config(&mut self) -> Result<Config, Error<I2C::Error>> {
+        let bits = self.read_register(Register::Config)?;
+        let config = V::decode_config(bits).ok_or(Error::InvalidConfig)?;
+        self.extended_mode = config.extended_mode();
+        self.conversion_rate = config.conversion_rate();
+        Ok(config)
+    }
+
+    /// Write the configuration register
+    ///
+    /// The SD bit is forced to match `MODE`; use the `into_*` transitions to
+    /// change the power mode. Fields the variant does not have are ignored.
+    pub fn write_config(&mut self, config: Config) -> Result<(), Error<I2C::Error>> {
+        self.write_config_register(config.with_shutdown(MODE::SHUTDOWN))
+    }
+
+    /// Whether the driver is decoding readings in extended (13-bit) mode
+    pub fn extended_mode(&self) -> bool {
+        self.extended_mode
+    }
+
+    /// Maximum error of this sensor variant (datasheet)
+    pub fn accuracy(&self) -> Temperature {
+        V::ACCURACY
+    }
+
+    /// Time between conversions in continuous mode
+    ///
+    /// Follows the conversion rate last written or read back on variants
+    /// that have one.
+    pub fn conversion_period_us(&self) -> u32 {
+        if V::HAS_CONVERSION_RATE {
+            self.conversion_rate.period_us()
+        } else {
+            V::CONVERSION_PERIOD_US
+        }
+    }
+
+    /// Set the lower alert threshold (T_LOW)
+    ///
+    /// Values are clamped to the range of the current data format and
+    /// rounded down to the variant's resolution. The sensor does not convert
+    /// thresholds when extended mode changes, so set them afterwards.
+    pub fn set_low_threshold(&mut self, temp: Temperature) -> Result<(), Error<I2C::Error>> {
+        let raw = variant::encode::<V>(temp, self.extended_mode);
+        self.write_register(Register::TLow, raw)
+    }
+
+    /// Set the upper alert threshold (T_HIGH)
+    ///
+    /// See [`Sensor::set_low_threshold`] for the valid range.
+    pub fn set_high_threshold(&mut self, temp: Temperature) -> Result<(), Error<I2C::Error>> {
+        let raw = variant::encode::<V>(temp, self.extended_mode);
+        self.write_register(Register::THigh, raw)
+    }
+
+    /// Read the lower alert threshold (T_LOW)
+    pub fn read_low_threshold(&mut self) -> Result<Temperature, Error<I2C::Error>> {
+        let raw = self.read_register(Register::TLow)?;
+        Ok(variant::decode::<V>(raw, self.extended_mode))
+    }
+
+    /// Read the upper alert threshold (T_HIGH)
+    pub fn read_high_threshold(&mut self) -> Result<Temperature, Error<I2C::Error>> {
+        let raw = self.read_register(Register::THigh)?;
+        Ok(variant::decode::<V>(raw, self.extended_mode))
+    }
+
+    /// Select comparator or interrupt behavior for the ALERT pin
+    pub fn set_thermostat_mode(&mut self, mode: ThermostatMode) -> Result<(), Error<I2C::Error>> {
+        self.modify_config(|c| c.with_thermostat_mode(mode))
+    }
+
+    /// Set the number of consecutive faults needed to trigger the alert
+    pub fn set_fault_queue(&mut self, fault_queue: FaultQueue) -> Result<(), Error<I2C::Error>> {
+        self.modify_config(|c| c.with_fault_queue(fault_queue))
+    }
+
+    /// Set the active level of the ALERT pin
+    pub fn set_alert_polarity(&mut self, polarity: AlertPolarity) -> Result<(), Error<I2C::Error>> {
+        self.modify_config(|c| c.with_alert_polarity(polarity))
+    }
+
+    /// Read a register as raw bits
+    ///
+    /// 8-bit configuration registers (TMP75, LM75) are returned in the upper
+    /// byte, matching the [`Config`] layout.
+    pub fn read_register(&mut self, register: Register) -> Result<u16, Error<I2C::Error>> {
+        let address = self.address.as_u8();
+        let pointer = [register.as_u8()];
+        if register == Register::Config && V::CONFIG_BYTES == 1 {
+            let mut rx_buf = [0u8; 1];
+            self.i2c
+                .write_read(address, &pointer, &mut rx_buf)
+                .map_err(Error::from)?;
+            return Ok(u16::from(rx_buf[0]) << 8);
+        }
         let mut rx_buf = [0u8; 2];
+        self.i2c
+            .write_read(address, &pointer, &mut rx_buf)
+            .map_err(Error::from)?;
+        Ok(u16::from_be_bytes(rx_buf))
+    }
 
-        // Read from sensor
-        match self
-            .i2c
-            .write_read(self.address.as_u8(), &[Register::TEMPERATURE], &mut rx_buf)
-        {
-            Ok(()) => Ok(self.raw_to_celsius(rx_buf)),
-            Err(e) => Err(Error::Communication(e)),
+    /// Write raw bits to a register (MSB first)
+    ///
+    /// Meant for diagnostics: a raw configuration write is not checked
+    /// against `MODE`, so it can leave the sensor in a different power mode
+    /// than the driver's type says. The temperature register is read-only.
+    pub fn write_register(
+        &mut self,
+        register: Register,
+        value: u16,
+    ) -> Result<(), Error<I2C::Error>> {
+        let address = self.address.as_u8();
+        let [msb, lsb] = value.to_be_bytes();
+        let result = if register == Register::Config && V::CONFIG_BYTES == 1 {
+            self.i2c.write(address, &[register.as_u8(), msb])
+        } else {
+            self.i2c.write(address, &[register.as_u8(), msb, lsb])
+        };
+        result.map_err(Error::from)?;
+
+        // Keep decoding readings in the format just selected
+        if register == Register::Config {
+            let config = Config::from_bits(value);
+            self.extended_mode = V::HAS_EXTENDED_MODE && config.extended_mode();
+            self.conversion_rate = config.conversion_rate();
+            self.cache = None;
         }
+        Ok(())
+    }
+
+    /// Read all four registers, e.g. for a debug console
+    pub fn dump_registers(&mut self) -> Result<RegisterDump, Error<I2C::Error>> {
+        Ok(RegisterDump {
+            temperature: self.read_register(Register::Temperature)?,
+            config: self.read_register(Register::Config)?,
+            t_low: self.read_register(Register::TLow)?,
+            t_high: self.read_register(Register::THigh)?,
+        })
+    }
+
+    /// Release the I2C bus
+    ///
+    /// The sensor keeps running in its current mode.
+    pub fn destroy(self) -> I2C {
+        self.i2c
     }
 
-    /// Convert raw reading to Celsius
-    fn raw_to_celsius(&self, buf: [u8; 2]) -> f32 {
-        let temp_raw = ((buf[0] as u16) << 8) | (buf[1] as u16);
-        let temp_signed = (temp_raw as i16) >> 4;
-        (temp_signed as f32) * 0.0625
+    /// Read the temperature register
+    fn fetch_temperature(&mut self) -> Result<Temperature, Error<I2C::Error>> {
+        let raw = self.read_register(Register::Temperature)?;
+        Ok(variant::decode::<V>(raw, self.extended_mode))
+    }
+
+    /// Set or clear SD, bypassing the `MODE` check in `write_config`
+    fn write_shutdown(&mut self, shutdown: bool) -> Result<(), Error<I2C::Error>> {
+        let config = self.read_config()?.with_one_shot(false);
+        self.write_config_register(config.with_shutdown(shutdown))
+    }
+
+    /// Hand the bus over to a driver in another mode
+    fn into_mode<NEW>(self) -> Sensor<I2C, V, NEW> {
+        Sensor {
+            i2c: self.i2c,
+            address: self.address,
+            extended_mode: self.extended_mode,
+            conversion_rate: self.conversion_rate,
+            cache: None,
+            variant: PhantomData,
+            mode: PhantomData,
+        }
+    }
+
+    /// Read-modify-write the configuration register
+    ///
+    /// OS is cleared first so a finished one-shot result (OS reads 1) is not
+    /// written back as a request for another conversion.
+    fn modify_config<F>(&mut self, f: F) -> Result<(), Error<I2C::Error>>
+    where
+        F: FnOnce(Config) -> Config,
+    {
+        let config = self.read_config()?.with_one_shot(false);
+        self.write_config(f(config))
+    }
+
+    /// Encode and write the configuration register as given
+    fn write_config_register(&mut self, config: Config) -> Result<(), Error<I2C::Error>> {
+        self.write_register(Register::Config, V::encode_config(config))
     }
 }
 
@@ -93,44 +487,72 @@ mod tests {
     extern crate std;
 
     // Test-only imports
-    use embedded_hal::i2c::{Error as I2cError, ErrorKind, Operation};
+    use core::cell::Cell;
+    use embedded_hal::i2c::{Error as I2cError, ErrorKind, NoAcknowledgeSource, Operation};
+    use i2c_mock::{I2cMock, Transaction};
 
     // I2C stub
     #[derive(Debug)]
     pub struct I2cStub {
-        pub response_data: [u8; 2],
+        pub registers: [[u8; 2]; 4],
+        pub pointer: usize,
         pub call_count: usize,
+        pub converting: bool,
+        pub fail_with: Option<ErrorKind>,
     }
 
     // I2C bus with temperature sensor stub implementation
     impl I2cStub {
-
         // Create a new I2C bus
         pub fn new() -> Self {
             Self {
-                response_data: [0x00, 0x00],
+                registers: [[0x00, 0x00], [0x60, 0xA0], [0x4B, 0x00], [0x50, 0x00]],
+                pointer: 0,
                 call_count: 0,
+                converting: false,
+                fail_with: None,
             }
         }
 
         // Set temperature (in Celsius)
         pub fn set_temperature(&mut self, temp_c: f32) {
-            // Convert temperature to sensor format
-            let temp_raw = ((temp_c / 0.0625) as i16) << 4;
-            self.response_data[0] = (temp_raw >> 8) as u8;
-            self.response_data[1] = (temp_raw & 0xFF) as u8;
+            // Convert temperature to sensor format, rounding to the nearest
+            // count (a plain cast truncates toward zero)
+            let counts = (temp_c / 0.0625).round() as i32;
+            let temp_raw = ((counts.clamp(-2048, 2047) & 0x0FFF) << 4) as u16;
+            self.registers[0] = temp_raw.to_be_bytes();
         }
     }
 
-    // Declare a dummy error type
+    // Delay stub that only adds up the requested time
+    #[derive(Debug, Default)]
+    pub struct DelayStub {
+        pub total_ns: u64,
+    }
+
+    impl DelayNs for DelayStub {
+        fn delay_ns(&mut self, ns: u32) {
+            self.total_ns += ns as u64;
+        }
+    }
+
+    // Async delay stub: never actually waits
+    #[cfg(feature = "async")]
+    impl embedded_hal_async::delay::DelayNs for DelayStub {
+        async fn delay_ns(&mut self, ns: u32) {
+            self.total_ns += ns as u64;
+        }
+    }
+
+    // Declare a dummy error type that carries the kind to report
     #[derive(Debug, Clone)]
-    pub struct DummyError;
+    pub struct DummyError(pub ErrorKind);
 
     // Implement I2cError trait for the DummyError type
     impl I2cError for DummyError {
-       fn kind(&self) -> ErrorKind {
-        ErrorKind::Other
-       }
+        fn kind(&self) -> ErrorKind {
+            self.0
+        }
     }
 
     // Associated type: use our dummy error type (for e.g. Self::Error)
@@ -140,35 +562,43 @@ mod tests {
 
     // Stub mplementations of the basic I2C read/write functions
     impl embedded_hal::i2c::I2c for I2cStub {
-
         // Always return Ok
-        fn read(
-            &mut self, 
-            _address: u8, 
-            _read: &mut [u8]
-        ) -> Result<(), Self::Error> {
+        fn read(&mut self, _address: u8, _read: &mut [u8]) -> Result<(), Self::Error> {
             Ok(())
         }
 
-        // Always return Ok
-        fn write(
-            &mut self, 
-            _address: u8, 
-            _write: &[u8]
-        ) -> Result<(), Self::Error> {
+        // Set the pointer register and store any data that follows it
+        fn write(&mut self, _address: u8, write: &[u8]) -> Result<(), Self::Error> {
+            if let Some(kind) = self.fail_with {
+                return Err(DummyError(kind));
+            }
+            if let [pointer, data @ ..] = write {
+                self.pointer = (*pointer & 0x03) as usize;
+                if let [msb, lsb] = data {
+                    self.registers[self.pointer] = [*msb, *lsb];
+                }
+            }
             Ok(())
         }
 
-        // Always return Ok
+        // Set the pointer register and return that register's contents
         fn write_read(
             &mut self,
             _address: u8,
-            _write: &[u8],
+            write: &[u8],
             read: &mut [u8],
         ) -> Result<(), Self::Error> {
-            // Return canned response
-            read.copy_from_slice(&self.response_data);
+            if let Some(kind) = self.fail_with {
+                return Err(DummyError(kind));
+            }
+            self.pointer = (write[0] & 0x03) as usize;
+            read.copy_from_slice(&self.registers[self.pointer]);
             self.call_count += 1;
+
+            // OS bit reads as 0 while a one-shot conversion is in progress
+            if self.pointer == 1 && self.converting {
+                read[0] &= 0x7F;
+            }
             Ok(())
         }
 
@@ -182,6 +612,35 @@ mod tests {
         }
     }
 
+    // Async I2C uses the same register model as the blocking stub
+    #[cfg(feature = "async")]
+    impl embedded_hal_async::i2c::I2c for I2cStub {
+        async fn read(&mut self, address: u8, read: &mut [u8]) -> Result<(), Self::Error> {
+            embedded_hal::i2c::I2c::read(self, address, read)
+        }
+
+        async fn write(&mut self, address: u8, write: &[u8]) -> Result<(), Self::Error> {
+            embedded_hal::i2c::I2c::write(self, address, write)
+        }
+
+        async fn write_read(
+            &mut self,
+            address: u8,
+            write: &[u8],
+            read: &mut [u8],
+        ) -> Result<(), Self::Error> {
+            embedded_hal::i2c::I2c::write_read(self, address, write, read)
+        }
+
+        async fn transaction(
+            &mut self,
+            address: u8,
+            operations: &mut [Operation<'_>],
+        ) -> Result<(), Self::Error> {
+            embedded_hal::i2c::I2c::transaction(self, address, operations)
+        }
+    }
+
     // Unit test 1: create a new driver and make sure the device address is set
     #[test]
     fn test_new_driver() {
@@ -205,4 +664,567 @@ mod tests {
 
         assert_eq!(temp, 25.0);
     }
-}
\ No newline at end of file
+
+    // Unit test 3: Read the power-on configuration
+    #[test]
+    fn test_read_config() {
+        let i2c = I2cStub::new();
+        let mut driver = TMP102::new(i2c, Address::Ground);
+        let config = driver.read_config().unwrap();
+
+        assert_eq!(config, Config::default());
+        assert_eq!(config.conversion_rate(), ConversionRate::Hz4);
+        assert!(config.alert());
+        assert_eq!(driver.i2c.pointer, 1);
+    }
+
+    // Unit test 4: Write the configuration register (MSB first)
+    #[test]
+    fn test_write_config() {
+        let i2c = I2cStub::new();
+        let mut driver = TMP102::new(i2c, Address::Ground).into_shutdown().unwrap();
+        let config = Config::default()
+            .with_shutdown(true)
+            .with_thermostat_mode(ThermostatMode::Interrupt)
+            .with_alert_polarity(AlertPolarity::ActiveHigh)
+            .with_fault_queue(FaultQueue::Four)
+            .with_conversion_rate(ConversionRate::Hz8);
+        driver.write_config(config).unwrap();
+
+        assert_eq!(driver.i2c.registers[1], [0x77, 0xE0]);
+        assert_eq!(driver.read_config().unwrap(), config);
+    }
+
+    // Unit test 5: Decode every 12-bit code from -55 °C up to the 128 °C limit
+    #[test]
+    fn test_temperature_range_normal() {
+        let mut driver = TMP102::new(I2cStub::new(), Address::Ground);
+        for counts in (-55 * 16)..=0x7FF {
+            let raw = (counts << 4) as u16;
+            driver.i2c.registers[0] = raw.to_be_bytes();
+            let temp = driver.read_temperature().unwrap();
+            assert_eq!(temp, Temperature::from_counts(counts));
+        }
+    }
+
+    // Unit test 6: Decode every 13-bit code from -55 °C to 150 °C
+    #[test]
+    fn test_temperature_range_extended() {
+        let mut driver = TMP102::new(I2cStub::new(), Address::Ground);
+        driver.set_extended_mode(true).unwrap();
+        assert_eq!(driver.i2c.registers[1], [0x60, 0xB0]);
+        for counts in (-55 * 16)..=(150 * 16) {
+            // Bit 0 of the temperature register flags extended mode
+            let raw = ((counts << 3) as u16) | 0x0001;
+            driver.i2c.registers[0] = raw.to_be_bytes();
+            let temp = driver.read_temperature().unwrap();
+            assert_eq!(temp, Temperature::from_counts(counts));
+        }
+    }
+
+    // Unit test 7: Datasheet examples in both formats
+    #[test]
+    fn test_datasheet_examples() {
+        let mut driver = TMP102::new(I2cStub::new(), Address::Ground);
+        let normal = [
+            (0x7FF0, 127.9375),
+            (0x1900, 25.0),
+            (0x0040, 0.25),
+            (0x0000, 0.0),
+            (0xFFC0, -0.25),
+            (0xE700, -25.0),
+            (0xC900, -55.0),
+        ];
+        for (raw, expected) in normal {
+            driver.i2c.registers[0] = u16::to_be_bytes(raw);
+            assert_eq!(driver.read_temperature_c().unwrap(), expected);
+        }
+
+        driver.set_extended_mode(true).unwrap();
+        let extended = [
+            (0x4B01, 150.0),
+            (0x4001, 128.0),
+            (0x3F81, 127.0),
+            (0x0C81, 25.0),
+            (0x0001, 0.0),
+            (0xFFE1, -0.25),
+            (0xE481, -55.0),
+        ];
+        for (raw, expected) in extended {
+            driver.i2c.registers[0] = u16::to_be_bytes(raw);
+            assert_eq!(driver.read_temperature_c().unwrap(), expected);
+        }
+    }
+
+    // Unit test 8: Extended mode setting follows configuration reads
+    #[test]
+    fn test_extended_mode_tracking() {
+        let mut driver = TMP102::new(I2cStub::new(), Address::Ground);
+        assert!(!driver.extended_mode());
+
+        // Another owner of the sensor enabled EM behind our back
+        driver.i2c.registers[1] = [0x60, 0xB0];
+        driver.read_config().unwrap();
+        assert!(driver.extended_mode());
+
+        driver.set_extended_mode(false).unwrap();
+        assert!(!driver.extended_mode());
+        assert_eq!(driver.i2c.registers[1], [0x60, 0xA0]);
+    }
+
+    // Unit test 9: Thresholds are written left-justified, MSB first
+    #[test]
+    fn test_threshold_encoding() {
+        let mut driver = TMP102::new(I2cStub::new(), Address::Ground);
+
+        // Power-on defaults
+        let low = driver.read_low_threshold().unwrap();
+        let high = driver.read_high_threshold().unwrap();
+        assert_eq!(low, Temperature::from_celsius(75));
+        assert_eq!(high, Temperature::from_celsius(80));
+
+        let low = Temperature::from_millicelsius(-10_063);
+        let high = Temperature::from_celsius(30);
+        driver.set_low_threshold(low).unwrap();
+        driver.set_high_threshold(high).unwrap();
+        assert_eq!(driver.i2c.registers[2], [0xF5, 0xF0]);
+        assert_eq!(driver.i2c.registers[3], [0x1E, 0x00]);
+        assert_eq!(driver.read_low_threshold().unwrap(), low);
+        assert_eq!(driver.read_high_threshold().unwrap(), high);
+
+        // Out-of-range values saturate at the 12-bit limit
+        let too_high = Temperature::from_celsius(150);
+        let too_low = Temperature::from_celsius(-300);
+        driver.set_high_threshold(too_high).unwrap();
+        assert_eq!(driver.i2c.registers[3], [0x7F, 0xF0]);
+        driver.set_low_threshold(too_low).unwrap();
+        assert_eq!(driver.i2c.registers[2], [0x80, 0x00]);
+    }
+
+    // Unit test 10: Thresholds use the 13-bit format in extended mode
+    #[test]
+    fn test_threshold_encoding_extended() {
+        let mut driver = TMP102::new(I2cStub::new(), Address::Ground);
+        driver.set_extended_mode(true).unwrap();
+
+        let high = Temperature::from_celsius(150);
+        let low = Temperature::from_celsius(-55);
+        driver.set_high_threshold(high).unwrap();
+        driver.set_low_threshold(low).unwrap();
+        assert_eq!(driver.i2c.registers[3], [0x4B, 0x00]);
+        assert_eq!(driver.i2c.registers[2], [0xE4, 0x80]);
+        assert_eq!(driver.read_high_threshold().unwrap(), high);
+        assert_eq!(driver.read_low_threshold().unwrap(), low);
+    }
+
+    // Unit test 11: Alert settings only touch their own configuration bits
+    #[test]
+    fn test_alert_settings() {
+        let mut driver = TMP102::new(I2cStub::new(), Address::Ground);
+        driver
+            .set_thermostat_mode(ThermostatMode::Interrupt)
+            .unwrap();
+        driver.set_fault_queue(FaultQueue::Six).unwrap();
+        driver
+            .set_alert_polarity(AlertPolarity::ActiveHigh)
+            .unwrap();
+
+        let expected = Config::default()
+            .with_thermostat_mode(ThermostatMode::Interrupt)
+            .with_fault_queue(FaultQueue::Six)
+            .with_alert_polarity(AlertPolarity::ActiveHigh);
+        assert_eq!(driver.read_config().unwrap(), expected);
+        assert_eq!(driver.i2c.registers[1], [0x7E, 0xA0]);
+    }
+
+    // Unit test 12: Mode transitions only change the SD bit
+    #[test]
+    fn test_shutdown() {
+        let driver = TMP102::new(I2cStub::new(), Address::Ground);
+        let mut driver = driver.into_shutdown().unwrap();
+        assert_eq!(driver.i2c.registers[1], [0x61, 0xA0]);
+
+        // Configuration writes cannot leave shutdown by accident
+        driver.write_config(Config::default()).unwrap();
+        assert_eq!(driver.i2c.registers[1], [0x61, 0xA0]);
+
+        let driver = driver.into_continuous().unwrap();
+        assert_eq!(driver.i2c.registers[1], [0x60, 0xA0]);
+    }
+
+    // Unit test 13: Non-blocking one-shot conversion
+    #[test]
+    fn test_start_and_poll_conversion() {
+        let driver = TMP102::new(I2cStub::new(), Address::Ground);
+        let mut driver = driver.into_shutdown().unwrap();
+        driver.i2c.set_temperature(-12.5);
+        driver.i2c.converting = true;
+
+        // Start sets OS, keeping SD
+        driver.start_conversion().unwrap();
+        assert_eq!(driver.i2c.registers[1], [0xE1, 0xA0]);
+
+        // Still converting
+        assert_eq!(driver.poll_conversion().unwrap(), None);
+
+        // Conversion done
+        driver.i2c.converting = false;
+        let expected = Temperature::from_millicelsius(-12_500);
+        assert_eq!(driver.poll_conversion().unwrap(), Some(expected));
+    }
+
+    // Unit test 14: Blocking one-shot read waits for the conversion
+    #[test]
+    fn test_read_temperature_one_shot() {
+        let driver = TMP102::new(I2cStub::new(), Address::Ground);
+        let mut driver = driver.into_shutdown().unwrap();
+        driver.i2c.set_temperature(21.75);
+        let mut delay = DelayStub::default();
+
+        let temp = driver.read_temperature_one_shot(&mut delay).unwrap();
+        assert_eq!(temp.as_millicelsius(), 21_750);
+        assert_eq!(delay.total_ns, 26_000_000);
+        assert!(driver.read_config().unwrap().shutdown());
+    }
+
+    // Unit test 15: Blocking one-shot read gives up if OS never sets
+    #[test]
+    fn test_read_temperature_one_shot_timeout() {
+        let driver = TMP102::new(I2cStub::new(), Address::Ground);
+        let mut driver = driver.into_shutdown().unwrap();
+        driver.i2c.converting = true;
+        let mut delay = DelayStub::default();
+
+        let result = driver.read_temperature_one_shot(&mut delay);
+        assert!(matches!(result, Err(Error::ConversionTimeout)));
+        assert_eq!(delay.total_ns, 46_000_000);
+    }
+
+    // Unit test 16: Bus errors are sorted by kind
+    #[test]
+    fn test_bus_errors() {
+        let mut driver = TMP102::new(I2cStub::new(), Address::Ground);
+
+        let nack = ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address);
+        driver.i2c.fail_with = Some(nack);
+        let result = driver.read_temperature();
+        assert!(matches!(result, Err(Error::NoAcknowledge(_))));
+
+        driver.i2c.fail_with = Some(ErrorKind::ArbitrationLoss);
+        let result = driver.read_config();
+        assert!(matches!(result, Err(Error::ArbitrationLoss(_))));
+
+        driver.i2c.fail_with = Some(ErrorKind::Bus);
+        let result = driver.write_config(Config::default());
+        assert!(matches!(result, Err(Error::Bus(_))));
+
+        driver.i2c.fail_with = Some(ErrorKind::Other);
+        let result = driver.read_temperature();
+        assert!(matches!(result, Err(Error::Communication(_))));
+    }
+
+    // Unit test 17: A floating bus fails the configuration readback check
+    #[test]
+    fn test_invalid_config() {
+        let mut driver = TMP102::new(I2cStub::new(), Address::Ground);
+        driver.i2c.registers[1] = [0xFF, 0xFF];
+        assert!(matches!(driver.read_config(), Err(Error::InvalidConfig)));
+        let result = driver.into_shutdown();
+        assert!(matches!(result, Err(Error::InvalidConfig)));
+    }
+
+    // Unit test 18: Register pointers and byte order on the bus
+    #[test]
+    fn test_transactions() {
+        let mut driver = TMP102::with_default_address(I2cMock::new(&[
+            Transaction::write_read(0x48, &[0x00], &[0x19, 0x10]),
+            Transaction::write(0x48, &[0x03, 0x28, 0x00]),
+            Transaction::write(0x48, &[0x02, 0xE7, 0x00]),
+            Transaction::write_read(0x48, &[0x03], &[0x28, 0x00]),
+        ]));
+
+        assert_eq!(driver.read_temperature().unwrap().counts(), 401);
+        driver
+            .set_high_threshold(Temperature::from_celsius(40))
+            .unwrap();
+        driver
+            .set_low_threshold(Temperature::from_celsius(-25))
+            .unwrap();
+        assert_eq!(
+            driver.read_high_threshold().unwrap(),
+            Temperature::from_celsius(40)
+        );
+        driver.i2c.done();
+    }
+
+    // Unit test 19: One-shot read is a read-modify-write then OS polling
+    #[test]
+    fn test_one_shot_transactions() {
+        let driver = TMP102::with_default_address(I2cMock::new(&[
+            Transaction::write_read(0x48, &[0x01], &[0x60, 0xA0]),
+            Transaction::write(0x48, &[0x01, 0x61, 0xA0]),
+            Transaction::write_read(0x48, &[0x01], &[0x61, 0xA0]),
+            Transaction::write(0x48, &[0x01, 0xE1, 0xA0]),
+            Transaction::write_read(0x48, &[0x01], &[0x61, 0xA0]),
+            Transaction::write_read(0x48, &[0x01], &[0xE1, 0xA0]),
+            Transaction::write_read(0x48, &[0x00], &[0xE7, 0x00]),
+        ]));
+        let mut driver = driver.into_shutdown().unwrap();
+        let mut delay = DelayStub::default();
+
+        let temp = driver.read_temperature_one_shot(&mut delay).unwrap();
+        assert_eq!(temp, Temperature::from_celsius(-25));
+        assert_eq!(delay.total_ns, 28_000_000);
+        driver.i2c.done();
+    }
+
+    // Unit test 20: Errors from the bus stop the sequence
+    #[test]
+    fn test_error_transactions() {
+        let nack = ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address);
+        let mut i2c =
+            I2cMock::new(&[Transaction::write_read(0x49, &[0x01], &[0x60, 0xA0]).with_error(nack)]);
+        let driver = TMP102::new(i2c.clone(), Address::Vdd);
+
+        let result = driver.into_shutdown();
+        assert!(matches!(result, Err(Error::NoAcknowledge(_))));
+        i2c.done();
+    }
+
+    // Unit test 21: TMP112 uses the TMP102 register layout
+    #[test]
+    fn test_tmp112() {
+        let mut driver = TMP112::with_default_address(I2cMock::new(&[
+            Transaction::write_read(0x48, &[0x01], &[0x60, 0xA0]),
+            Transaction::write(0x48, &[0x01, 0x60, 0xB0]),
+            Transaction::write_read(0x48, &[0x00], &[0x4B, 0x01]),
+        ]));
+
+        assert_eq!(driver.accuracy().as_millicelsius(), 500);
+        driver.set_extended_mode(true).unwrap();
+        let temp = driver.read_temperature().unwrap();
+        assert_eq!(temp, Temperature::from_celsius(150));
+        driver.i2c.done();
+    }
+
+    // Unit test 22: TMP75 has an 8-bit configuration register
+    #[test]
+    fn test_tmp75() {
+        let driver = TMP75::new(
+            I2cMock::new(&[
+                Transaction::write_read(0x49, &[0x01], &[0x00]),
+                Transaction::write(0x49, &[0x01, 0x61]),
+                Transaction::write_read(0x49, &[0x01], &[0x61]),
+                Transaction::write(0x49, &[0x01, 0xE1]),
+                Transaction::write_read(0x49, &[0x00], &[0x19, 0x10]),
+            ]),
+            Address::Vdd,
+        );
+        let mut driver = driver.into_shutdown().unwrap();
+        let mut delay = DelayStub::default();
+
+        // No ready flag, so the full conversion time is waited out
+        let temp = driver.read_temperature_one_shot(&mut delay).unwrap();
+        assert_eq!(temp.counts(), 401);
+        assert_eq!(delay.total_ns, 300_000_000);
+        driver.i2c.done();
+    }
+
+    // Unit test 23: LM75 readings and thresholds have 0.5 °C resolution
+    #[test]
+    fn test_lm75() {
+        let mut driver = LM75::with_default_address(I2cMock::new(&[
+            Transaction::write_read(0x48, &[0x00], &[0x19, 0x7F]),
+            Transaction::write(0x48, &[0x03, 0x50, 0x00]),
+            Transaction::write_read(0x48, &[0x01], &[0x00]),
+            Transaction::write(0x48, &[0x01, 0x02]),
+            Transaction::write_read(0x48, &[0x01], &[0xE0]),
+        ]));
+
+        let temp = driver.read_temperature().unwrap();
+        assert_eq!(temp, Temperature::from_celsius(25));
+        let threshold = Temperature::from_millicelsius(80_300);
+        driver.set_high_threshold(threshold).unwrap();
+        driver
+            .set_thermostat_mode(ThermostatMode::Interrupt)
+            .unwrap();
+        assert!(matches!(driver.read_config(), Err(Error::InvalidConfig)));
+        driver.i2c.done();
+    }
+
+    // Unit test 24: Raw register access and dump
+    #[test]
+    fn test_register_access() {
+        let mut driver = TMP102::new(I2cStub::new(), Address::Ground);
+        driver.i2c.set_temperature(25.0);
+
+        let dump = driver.dump_registers().unwrap();
+        assert_eq!(
+            dump,
+            RegisterDump {
+                temperature: 0x1900,
+                config: 0x60A0,
+                t_low: 0x4B00,
+                t_high: 0x5000,
+            }
+        );
+
+        // A raw configuration write still switches the data format
+        driver.write_register(Register::Config, 0x60B0).unwrap();
+        assert!(driver.extended_mode());
+        assert_eq!(driver.read_register(Register::Config).unwrap(), 0x60B0);
+    }
+
+    // Unit test 25: 8-bit configuration registers use one data byte
+    #[test]
+    fn test_register_access_8_bit() {
+        let mut driver = LM75::with_default_address(I2cMock::new(&[
+            Transaction::write_read(0x48, &[0x01], &[0x18]),
+            Transaction::write(0x48, &[0x01, 0x02]),
+            Transaction::write_read(0x48, &[0x02], &[0x4B, 0x00]),
+        ]));
+
+        assert_eq!(driver.read_register(Register::Config).unwrap(), 0x1800);
+        driver.write_register(Register::Config, 0x0200).unwrap();
+        assert_eq!(driver.read_register(Register::TLow).unwrap(), 0x4B00);
+        driver.i2c.done();
+    }
+
+    // Unit test 26: The bus can be taken back for other devices
+    #[test]
+    fn test_destroy() {
+        let driver = TMP102::new(I2cStub::new(), Address::Ground);
+        let driver = driver.into_shutdown().unwrap();
+        let i2c = driver.destroy();
+        assert_eq!(i2c.registers[1], [0x61, 0xA0]);
+    }
+
+    // Unit test 27: Cached reads only use the bus once per conversion period
+    #[test]
+    fn test_read_cached() {
+        let now = Cell::new(0u64);
+        let clock = || now.get();
+        let mut driver = TMP102::with_default_address(I2cStub::new());
+        driver.i2c.set_temperature(25.0);
+        assert_eq!(driver.conversion_period_us(), 250_000);
+
+        let reading = driver.read_temperature_cached(&clock).unwrap();
+        assert!(reading.fresh);
+        assert_eq!(reading.temperature, Temperature::from_celsius(25));
+        assert_eq!(driver.i2c.call_count, 1);
+
+        // Within 250 ms at the default 4 Hz: cached, no bus traffic
+        driver.i2c.set_temperature(30.0);
+        now.set(249_999);
+        let reading = driver.read_temperature_cached(&clock).unwrap();
+        assert!(!reading.fresh);
+        assert_eq!(reading.temperature, Temperature::from_celsius(25));
+        assert_eq!(driver.i2c.call_count, 1);
+
+        // A new conversion is due
+        now.set(250_000);
+        let reading = driver.read_temperature_cached(&clock).unwrap();
+        assert!(reading.fresh);
+        assert_eq!(reading.temperature, Temperature::from_celsius(30));
+
+        // A clock running backwards forces a read
+        now.set(100);
+        assert!(driver.read_temperature_cached(&clock).unwrap().fresh);
+    }
+
+    // Unit test 28: The cache follows the conversion rate and config writes
+    #[test]
+    fn test_read_cached_rate() {
+        let now = Cell::new(0u64);
+        let clock = || now.get();
+        let mut driver = TMP102::with_default_address(I2cStub::new());
+        driver.read_temperature_cached(&clock).unwrap();
+
+        // Writing the configuration drops the cache and sets the new period
+        let config = driver.read_config().unwrap();
+        driver
+            .write_config(config.with_conversion_rate(ConversionRate::Hz0_25))
+            .unwrap();
+        assert_eq!(driver.conversion_period_us(), 4_000_000);
+        assert!(driver.read_temperature_cached(&clock).unwrap().fresh);
+        now.set(3_999_999);
+        assert!(!driver.read_temperature_cached(&clock).unwrap().fresh);
+
+        // Variants without a rate setting use their fixed period
+        let lm75 = LM75::with_default_address(I2cStub::new());
+        assert_eq!(lm75.conversion_period_us(), 100_000);
+    }
+
+    // Unit test 29: Public types implement the traits of each optional feature
+    //
+    // Compile-time check; run under every combination of `defmt` and `serde`
+    #[test]
+    fn test_feature_traits() {
+        #[cfg(feature = "defmt")]
+        {
+            fn assert_format<T: defmt::Format>() {}
+            assert_format::<Address>();
+            assert_format::<Error<DummyError>>();
+            assert_format::<Temperature>();
+            assert_format::<Config>();
+            assert_format::<ConversionRate>();
+            assert_format::<FaultQueue>();
+            assert_format::<AlertPolarity>();
+            assert_format::<ThermostatMode>();
+            assert_format::<Register>();
+            assert_format::<RegisterDump>();
+            assert_format::<CachedReading>();
+            assert_format::<AlertState>();
+            assert_format::<AlertResponse>();
+            assert_format::<Reading<DummyError>>();
+        }
+
+        #[cfg(feature = "serde")]
+        {
+            use serde::Serialize;
+            use serde::de::DeserializeOwned;
+
+            fn assert_serde<T: Serialize + DeserializeOwned>() {}
+            fn assert_serialize<T: Serialize>() {}
+            assert_serde::<Address>();
+            assert_serde::<Temperature>();
+            assert_serde::<Config>();
+            assert_serde::<ConversionRate>();
+            assert_serde::<FaultQueue>();
+            assert_serde::<AlertPolarity>();
+            assert_serde::<ThermostatMode>();
+            assert_serde::<Register>();
+            assert_serde::<RegisterDump>();
+            assert_serde::<CachedReading>();
+            assert_serde::<AlertState>();
+            assert_serde::<AlertResponse>();
+            assert_serialize::<Error<DummyError>>();
+            assert_serialize::<Reading<DummyError>>();
+        }
+    }
+
+    // Unit test 30: Types serialize to readable telemetry and round-trip
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        // Temperatures are plain 1/16 °C counts
+        let temp = Temperature::from_celsius(25);
+        assert_eq!(serde_json::to_string(&temp).unwrap(), "400");
+
+        let config = Config::default()
+            .with_thermostat_mode(ThermostatMode::Interrupt)
+            .with_conversion_rate(ConversionRate::Hz8);
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(serde_json::from_str::<Config>(&json).unwrap(), config);
+
+        let address: Address = serde_json::from_str("\"Sda\"").unwrap();
+        assert_eq!(address.as_u8(), 0x4A);
+
+        // Errors keep only their kind
+        let error: Error<DummyError> = Error::ConversionTimeout;
+        assert_eq!(
+            serde_json::to_string(&error).unwrap(),
+            "\"ConversionTimeout\""
+        );
+    }
+}
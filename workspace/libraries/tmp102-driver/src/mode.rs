@@ -0,0 +1,41 @@
+//! Power mode typestates
+//!
+//! The driver's `MODE` parameter records whether the sensor is converting
+//! continuously or shut down, so the compiler rejects calls that make no
+//! sense in the current mode. Reading the temperature register of a sensor
+//! in shutdown, for example, returns whatever the last conversion left
+//! there, so [`crate::TMP102::read_temperature`] only exists in
+//! [`Continuous`] mode and the one-shot functions only exist in
+//! [`Shutdown`] mode.
+
+/// Sensor converts continuously at the configured rate (power-on default)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Continuous;
+
+/// Sensor is shut down and only converts on request (one-shot)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Shutdown;
+
+/// Power mode marker, implemented by [`Continuous`] and [`Shutdown`]
+///
+/// Switch modes with the consuming `into_shutdown()` and `into_continuous()`
+/// transitions, which hand back the driver in its new state.
+pub trait PowerMode: sealed::Sealed {
+    /// Value of the SD bit in this mode
+    const SHUTDOWN: bool;
+}
+
+impl PowerMode for Continuous {
+    const SHUTDOWN: bool = false;
+}
+
+impl PowerMode for Shutdown {
+    const SHUTDOWN: bool = true;
+}
+
+/// Keep other crates from adding modes the driver cannot handle
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for super::Continuous {}
+    impl Sealed for super::Shutdown {}
+}
@@ -0,0 +1,97 @@
+//! Register map and raw register access types
+//!
+//! These are for diagnostics. Normal use goes through the typed driver
+//! functions, which keep the driver's state (data format, power mode) in
+//! step with the sensor.
+
+use core::fmt;
+
+/// Internal registers, selected by the pointer register
+///
+/// LM75 datasheets call the threshold registers T_HYST and T_OS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Register {
+    /// Last conversion result (read-only)
+    Temperature = 0x00,
+    /// Configuration
+    Config = 0x01,
+    /// Lower alert threshold (T_LOW)
+    TLow = 0x02,
+    /// Upper alert threshold (T_HIGH)
+    THigh = 0x03,
+}
+
+impl Register {
+    /// All four registers, in pointer order
+    pub const ALL: [Register; 4] = [
+        Register::Temperature,
+        Register::Config,
+        Register::TLow,
+        Register::THigh,
+    ];
+
+    /// Get the pointer value in u8 format
+    pub fn as_u8(self) -> u8 {
+        self as u8
+    }
+}
+
+/// Raw contents of all four registers
+///
+/// 8-bit configuration registers (TMP75, LM75) are in the upper byte of
+/// `config`, matching the [`crate::Config`] layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegisterDump {
+    pub temperature: u16,
+    pub config: u16,
+    pub t_low: u16,
+    pub t_high: u16,
+}
+
+impl fmt::Display for RegisterDump {
+    /// One line, e.g. `TEMP=0x1900 CONFIG=0x60A0 T_LOW=0x4B00 T_HIGH=0x5000`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "TEMP=0x{:04X} CONFIG=0x{:04X} T_LOW=0x{:04X} T_HIGH=0x{:04X}",
+            self.temperature, self.config, self.t_low, self.t_high
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    // Import top-level structs/functions
+    use super::*;
+
+    // Explicitly link to std
+    extern crate std;
+    use std::format;
+
+    // Pointer values follow the datasheet
+    #[test]
+    fn test_pointers() {
+        let pointers = Register::ALL.map(Register::as_u8);
+        assert_eq!(pointers, [0x00, 0x01, 0x02, 0x03]);
+    }
+
+    // Dump prints on one line for a serial console
+    #[test]
+    fn test_display() {
+        let dump = RegisterDump {
+            temperature: 0x1900,
+            config: 0x60A0,
+            t_low: 0x4B00,
+            t_high: 0x5000,
+        };
+        assert_eq!(
+            format!("{}", dump),
+            "TEMP=0x1900 CONFIG=0x60A0 T_LOW=0x4B00 T_HIGH=0x5000"
+        );
+    }
+}
@@ -0,0 +1,725 @@
+//! Register-accurate TMP102 simulator for host testing
+//!
+//! [`Tmp102Sim`] implements `embedded_hal::i2c::I2c` (and the async trait
+//! with the `async` feature) and behaves like a TMP102 on the bus:
+//!
+//! - Only its own address is acknowledged; anything else is a NACK
+//! - Writes set the pointer register; three-byte writes also store data
+//! - R1/R0, AL and the low nibble of the configuration register are
+//!   read-only, and the temperature register cannot be written
+//! - OS starts a one-shot conversion in shutdown, reads 0 while converting
+//!   and 1 once the result is ready
+//! - The alert output follows comparator or interrupt mode, polarity and
+//!   the fault queue, and interrupt mode is cleared by any register read
+//! - An asserted interrupt-mode alert answers the SMBus Alert Response
+//!   Address with the sensor's address, then releases the pin
+//!
+//! There is no clock. In continuous mode each call to
+//! [`Tmp102Sim::set_temperature`] or [`Tmp102Sim::convert`] is one
+//! conversion. Temperatures are encoded here independently of the driver
+//! so the simulator can serve as a reference for its conversions, see
+//! [`encode_temperature`] and [`decode_temperature`].
+
+use embedded_hal::i2c::{self, ErrorKind, NoAcknowledgeSource, Operation};
+
+use crate::alert::ALERT_RESPONSE_ADDRESS;
+use crate::{Address, AlertPolarity, Config, FaultQueue, Temperature, ThermostatMode};
+
+/// Error reported by the simulator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SimError(pub ErrorKind);
+
+impl i2c::Error for SimError {
+    fn kind(&self) -> ErrorKind {
+        self.0
+    }
+}
+
+/// Which threshold crossing asserts the alert next in interrupt mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Watch {
+    High,
+    Low,
+}
+
+/// Simulated TMP102 on an I2C bus
+#[derive(Debug, Clone)]
+pub struct Tmp102Sim {
+    address: u8,
+    pointer: u8,
+    die_temperature: Temperature,
+    temperature: u16,
+    config: Config,
+    t_low: u16,
+    t_high: u16,
+    one_shot_ready: bool,
+    one_shot_remaining: Option<u32>,
+    conversion_polls: u32,
+    comparator_active: bool,
+    comparator_faults: u8,
+    interrupt_active: bool,
+    interrupt_watch: Watch,
+    interrupt_faults: u8,
+    conversions: u32,
+}
+
+impl Default for Tmp102Sim {
+    fn default() -> Self {
+        Self::new(Address::Ground)
+    }
+}
+
+impl Tmp102Sim {
+    /// Create a simulated sensor in its power-on state at 0 °C
+    pub fn new(address: Address) -> Self {
+        Self {
+            address: address.as_u8(),
+            pointer: 0,
+            die_temperature: Temperature::default(),
+            temperature: 0x0000,
+            config: Config::default(),
+            t_low: 0x4B00,
+            t_high: 0x5000,
+            one_shot_ready: false,
+            one_shot_remaining: None,
+            conversion_polls: 0,
+            comparator_active: false,
+            comparator_faults: 0,
+            interrupt_active: false,
+            interrupt_watch: Watch::High,
+            interrupt_faults: 0,
+            conversions: 0,
+        }
+    }
+
+    /// Set the die temperature (runs a conversion in continuous mode)
+    pub fn set_temperature(&mut self, temperature: Temperature) {
+        self.die_temperature = temperature;
+        if !self.config.shutdown() {
+            self.convert();
+        }
+    }
+
+    /// Store a raw conversion result, bypassing the encoding
+    ///
+    /// Reaches codes outside the sensor's range, e.g. to test decoding of
+    /// every 12- and 13-bit value. The alert logic is not updated.
+    pub fn set_raw_temperature(&mut self, raw: u16) {
+        self.temperature = raw;
+    }
+
+    /// Die temperature last set with [`Tmp102Sim::set_temperature`]
+    pub fn temperature(&self) -> Temperature {
+        self.die_temperature
+    }
+
+    /// Run one conversion cycle
+    ///
+    /// In continuous mode this updates the result and alert logic. In
+    /// shutdown it only finishes a pending one-shot conversion.
+    pub fn convert(&mut self) {
+        if self.config.shutdown() {
+            if self.one_shot_remaining.take().is_some() {
+                self.finish_conversion();
+                self.one_shot_ready = true;
+            }
+        } else {
+            self.finish_conversion();
+        }
+    }
+
+    /// Number of configuration reads that see OS = 0 during a one-shot
+    ///
+    /// The default of 0 makes the first poll after starting a conversion
+    /// see it finished. Use `u32::MAX` for a conversion that never ends.
+    pub fn set_conversion_polls(&mut self, polls: u32) {
+        self.conversion_polls = polls;
+    }
+
+    /// Total number of conversions performed
+    pub fn conversions(&self) -> u32 {
+        self.conversions
+    }
+
+    /// Whether the alert output is asserted
+    pub fn alert_active(&self) -> bool {
+        match self.config.thermostat_mode() {
+            ThermostatMode::Comparator => self.comparator_active,
+            ThermostatMode::Interrupt => self.interrupt_active,
+        }
+    }
+
+    /// Logic level of the ALERT pin (`true` = high)
+    pub fn alert_pin_high(&self) -> bool {
+        self.active_level(self.alert_active())
+    }
+
+    /// Current pointer register value
+    pub fn pointer(&self) -> u8 {
+        self.pointer
+    }
+
+    /// Raw register contents as the sensor would return them
+    pub fn register(&self, pointer: u8) -> u16 {
+        match pointer & 0x03 {
+            0 => self.temperature,
+            1 => self
+                .config
+                .with_one_shot(self.one_shot_ready)
+                .with_alert(self.active_level(self.comparator_active))
+                .to_bits(),
+            2 => self.t_low,
+            _ => self.t_high,
+        }
+    }
+
+    /// Map an active/inactive state to a level using POL
+    fn active_level(&self, active: bool) -> bool {
+        match self.config.alert_polarity() {
+            AlertPolarity::ActiveLow => !active,
+            AlertPolarity::ActiveHigh => active,
+        }
+    }
+
+    /// Latch the die temperature and update the alert logic
+    fn finish_conversion(&mut self) {
+        self.conversions += 1;
+        let extended = self.config.extended_mode();
+        self.temperature = encode_temperature(self.die_temperature, extended);
+
+        // Compare the stored result, as the sensor does
+        let value = decode_temperature(self.temperature, extended);
+        let high = value >= decode_temperature(self.t_high, extended);
+        let low = value < decode_temperature(self.t_low, extended);
+        let queue = fault_count(self.config.fault_queue());
+
+        // Comparator state drives AL (and the pin in comparator mode)
+        let fault = if self.comparator_active { low } else { high };
+        self.comparator_faults = if fault { self.comparator_faults + 1 } else { 0 };
+        if self.comparator_faults >= queue {
+            self.comparator_active = !self.comparator_active;
+            self.comparator_faults = 0;
+        }
+
+        // Interrupt mode alternates between watching T_HIGH and T_LOW
+        let fault = match self.interrupt_watch {
+            Watch::High => high,
+            Watch::Low => low,
+        };
+        self.interrupt_faults = if fault { self.interrupt_faults + 1 } else { 0 };
+        if self.interrupt_faults >= queue {
+            self.interrupt_active = true;
+            self.interrupt_faults = 0;
+            self.interrupt_watch = match self.interrupt_watch {
+                Watch::High => Watch::Low,
+                Watch::Low => Watch::High,
+            };
+        }
+    }
+
+    /// Handle a write of the pointer register and optional data
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        let Some((&pointer, data)) = bytes.split_first() else {
+            return;
+        };
+        self.pointer = pointer & 0x03;
+        let [msb, lsb] = match data {
+            [msb, lsb, ..] => [*msb, *lsb],
+            _ => return,
+        };
+        let value = u16::from_be_bytes([msb, lsb]);
+        match self.pointer {
+            0 => {} // Temperature register is read-only
+            1 => self.write_config(value),
+            2 => self.t_low = value,
+            _ => self.t_high = value,
+        }
+    }
+
+    /// Apply a configuration write, keeping read-only bits
+    fn write_config(&mut self, value: u16) {
+        let written = Config::from_bits(value);
+        let was_shutdown = self.config.shutdown();
+        self.config = written.with_one_shot(false);
+
+        // Entering shutdown clears an interrupt-mode alert
+        if written.shutdown() && !was_shutdown {
+            self.interrupt_active = false;
+        }
+
+        // OS only has an effect in shutdown mode
+        if written.shutdown() && written.one_shot() {
+            self.one_shot_ready = false;
+            self.one_shot_remaining = Some(self.conversion_polls);
+        } else if !written.shutdown() {
+            self.one_shot_ready = false;
+            self.one_shot_remaining = None;
+        }
+    }
+
+    /// Fill a read buffer from the current pointer register
+    fn read_bytes(&mut self, buf: &mut [u8]) {
+        // Polling the configuration register advances a one-shot conversion
+        if self.pointer == 1 {
+            match self.one_shot_remaining {
+                Some(0) => self.convert(),
+                Some(u32::MAX) | None => {}
+                Some(n) => self.one_shot_remaining = Some(n - 1),
+            }
+        }
+
+        let bytes = self.register(self.pointer).to_be_bytes();
+        for (dst, src) in buf.iter_mut().zip(bytes) {
+            *dst = src;
+        }
+
+        // Any read clears an interrupt-mode alert
+        self.interrupt_active = false;
+    }
+
+    /// Answer an alert response read if our interrupt-mode alert is active
+    fn alert_response(&mut self, operations: &mut [Operation<'_>]) -> Result<(), SimError> {
+        let asserting =
+            self.config.thermostat_mode() == ThermostatMode::Interrupt && self.interrupt_active;
+        if !asserting {
+            return Err(SimError(ErrorKind::NoAcknowledge(
+                NoAcknowledgeSource::Address,
+            )));
+        }
+
+        // LSB is 0 for a T_HIGH event with POL=0, inverted by POL=1
+        let over_temp = self.interrupt_watch == Watch::Low;
+        let active_high = self.config.alert_polarity() == AlertPolarity::ActiveHigh;
+        let response = (self.address << 1) | (over_temp == active_high) as u8;
+        for operation in operations {
+            if let Operation::Read(buf) = operation {
+                buf.fill(response);
+            }
+        }
+        self.interrupt_active = false;
+        Ok(())
+    }
+
+    /// NACK anything not sent to our address
+    fn check_address(&self, address: u8) -> Result<(), SimError> {
+        if address == self.address {
+            Ok(())
+        } else {
+            Err(SimError(ErrorKind::NoAcknowledge(
+                NoAcknowledgeSource::Address,
+            )))
+        }
+    }
+}
+
+/// Encode a temperature the way the sensor stores a conversion result
+///
+/// Reference implementation for tests: results are clamped to the sensor's
+/// -55 °C to 150 °C range (just below 128 °C in the 12-bit format), and
+/// bit 0 flags extended mode.
+pub fn encode_temperature(temperature: Temperature, extended: bool) -> u16 {
+    let counts = temperature.counts() as i32;
+    if extended {
+        // 13 bits, limited to the 150 °C range, bit 0 flags extended mode
+        let counts = counts.clamp(-55 * 16, 150 * 16);
+        (((counts & 0x1FFF) << 3) | 0x0001) as u16
+    } else {
+        // 12 bits, saturating at the format limit just below 128 °C
+        let counts = counts.clamp(-55 * 16, 0x7FF);
+        ((counts & 0x0FFF) << 4) as u16
+    }
+}
+
+/// Decode a left-justified register into 1/16 °C counts
+///
+/// Reference implementation for tests, by explicit two's complement on the
+/// 12- or 13-bit field rather than an arithmetic shift.
+pub fn decode_temperature(raw: u16, extended: bool) -> i32 {
+    let (value, bits) = if extended {
+        ((raw >> 3) as i32, 13)
+    } else {
+        ((raw >> 4) as i32, 12)
+    };
+    let sign = 1 << (bits - 1);
+    if value & sign != 0 {
+        value - (1 << bits)
+    } else {
+        value
+    }
+}
+
+/// Number of consecutive faults for a fault queue setting
+fn fault_count(queue: FaultQueue) -> u8 {
+    match queue {
+        FaultQueue::One => 1,
+        FaultQueue::Two => 2,
+        FaultQueue::Four => 4,
+        FaultQueue::Six => 6,
+    }
+}
+
+impl i2c::ErrorType for Tmp102Sim {
+    type Error = SimError;
+}
+
+impl i2c::I2c for Tmp102Sim {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        if address == ALERT_RESPONSE_ADDRESS {
+            return self.alert_response(operations);
+        }
+        self.check_address(address)?;
+        for operation in operations {
+            match operation {
+                Operation::Write(bytes) => self.write_bytes(bytes),
+                Operation::Read(buf) => self.read_bytes(buf),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "async")]
+impl embedded_hal_async::i2c::I2c for Tmp102Sim {
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        i2c::I2c::transaction(self, address, operations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    // Import top-level structs/functions
+    use super::*;
+
+    // Drive the simulator through the real driver
+    use crate::TMP102;
+    use crate::tests::DelayStub;
+
+    // Test-only imports
+    use core::cell::RefCell;
+    use embedded_hal::i2c::I2c;
+    use embedded_hal_bus::i2c::RefCellDevice;
+
+    // Power-on register contents
+    #[test]
+    fn test_power_on_state() {
+        let sim = Tmp102Sim::default();
+        assert_eq!(sim.register(0), 0x0000);
+        assert_eq!(sim.register(1), 0x60A0);
+        assert_eq!(sim.register(2), 0x4B00);
+        assert_eq!(sim.register(3), 0x5000);
+        assert!(!sim.alert_active());
+        assert!(sim.alert_pin_high());
+    }
+
+    // Conversion results in both data formats
+    #[test]
+    fn test_encoding() {
+        let mut sim = Tmp102Sim::default();
+        sim.set_temperature(Temperature::from_counts(-880));
+        assert_eq!(sim.register(0), 0xC900);
+        sim.set_temperature(Temperature::from_celsius(150));
+        assert_eq!(sim.register(0), 0x7FF0);
+
+        let mut tmp102 = TMP102::with_default_address(&mut sim);
+        tmp102.set_extended_mode(true).unwrap();
+        sim.convert();
+        assert_eq!(sim.register(0), 0x4B01);
+        sim.set_temperature(Temperature::from_counts(-1));
+        assert_eq!(sim.register(0), 0xFFF9);
+    }
+
+    // The driver decodes what the simulator encodes
+    #[test]
+    fn test_driver_reads() {
+        let bus = RefCell::new(Tmp102Sim::default());
+        let mut tmp102 = TMP102::with_default_address(RefCellDevice::new(&bus));
+
+        bus.borrow_mut()
+            .set_temperature(Temperature::from_counts(401));
+        assert_eq!(tmp102.read_temperature().unwrap().counts(), 401);
+
+        tmp102.set_extended_mode(true).unwrap();
+        bus.borrow_mut()
+            .set_temperature(Temperature::from_celsius(140));
+        assert_eq!(
+            tmp102.read_temperature().unwrap(),
+            Temperature::from_celsius(140)
+        );
+    }
+
+    // Pointer writes select a register; read-only bits are kept
+    #[test]
+    fn test_pointer_and_read_only_bits() {
+        let mut sim = Tmp102Sim::default();
+        let mut buf = [0u8; 2];
+
+        sim.write(0x48, &[0x02]).unwrap();
+        assert_eq!(sim.pointer(), 2);
+        sim.read(0x48, &mut buf).unwrap();
+        assert_eq!(buf, [0x4B, 0x00]);
+
+        // Temperature register ignores writes
+        sim.set_temperature(Temperature::from_celsius(25));
+        sim.write(0x48, &[0x00, 0x12, 0x34]).unwrap();
+        assert_eq!(sim.register(0), 0x1900);
+
+        // R1/R0, AL and the reserved nibble cannot be cleared or set
+        sim.write(0x48, &[0x01, 0x00, 0x0F]).unwrap();
+        assert_eq!(sim.register(1), 0x6020);
+    }
+
+    // Wrong addresses are not acknowledged
+    #[test]
+    fn test_nack() {
+        let mut sim = Tmp102Sim::new(Address::Vdd);
+        let e = sim.write(0x48, &[0x00]).unwrap_err();
+        assert_eq!(e.0, ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address));
+
+        let mut tmp102 = TMP102::with_default_address(&mut sim);
+        assert!(matches!(
+            tmp102.read_temperature(),
+            Err(crate::Error::NoAcknowledge(_))
+        ));
+    }
+
+    // One-shot conversions only happen in shutdown and report OS
+    #[test]
+    fn test_one_shot() {
+        let bus = RefCell::new(Tmp102Sim::default());
+        let tmp102 = TMP102::with_default_address(RefCellDevice::new(&bus));
+        bus.borrow_mut().set_conversion_polls(2);
+
+        let mut tmp102 = tmp102.into_shutdown().unwrap();
+        bus.borrow_mut()
+            .set_temperature(Temperature::from_celsius(30));
+        assert_eq!(bus.borrow().register(0), 0x0000);
+
+        tmp102.start_conversion().unwrap();
+        assert_eq!(tmp102.poll_conversion().unwrap(), None);
+        assert_eq!(tmp102.poll_conversion().unwrap(), None);
+        assert_eq!(
+            tmp102.poll_conversion().unwrap(),
+            Some(Temperature::from_celsius(30))
+        );
+        assert_eq!(bus.borrow().conversions(), 1);
+
+        // Blocking helper waits for the result
+        bus.borrow_mut()
+            .set_temperature(Temperature::from_celsius(-10));
+        let mut delay = DelayStub::default();
+        let t = tmp102.read_temperature_one_shot(&mut delay).unwrap();
+        assert_eq!(t, Temperature::from_celsius(-10));
+    }
+
+    // A conversion that never finishes times out
+    #[test]
+    fn test_one_shot_timeout() {
+        let mut sim = Tmp102Sim::default();
+        sim.set_conversion_polls(u32::MAX);
+        let tmp102 = TMP102::with_default_address(&mut sim);
+        let mut tmp102 = tmp102.into_shutdown().unwrap();
+
+        let mut delay = DelayStub::default();
+        assert!(matches!(
+            tmp102.read_temperature_one_shot(&mut delay),
+            Err(crate::Error::ConversionTimeout)
+        ));
+    }
+
+    // Comparator mode follows the thresholds with hysteresis
+    #[test]
+    fn test_comparator_alert() {
+        let bus = RefCell::new(Tmp102Sim::default());
+        let mut tmp102 = TMP102::with_default_address(RefCellDevice::new(&bus));
+        tmp102
+            .set_low_threshold(Temperature::from_celsius(30))
+            .unwrap();
+        tmp102
+            .set_high_threshold(Temperature::from_celsius(35))
+            .unwrap();
+
+        let set = |celsius| {
+            bus.borrow_mut()
+                .set_temperature(Temperature::from_celsius(celsius))
+        };
+        set(34);
+        assert!(!bus.borrow().alert_active());
+        set(35);
+        assert!(bus.borrow().alert_active());
+        assert!(!bus.borrow().alert_pin_high());
+        assert!(!tmp102.read_config().unwrap().alert());
+
+        // Reads do not clear comparator mode; it stays on until below T_LOW
+        set(31);
+        assert!(bus.borrow().alert_active());
+        set(29);
+        assert!(!bus.borrow().alert_active());
+        assert!(tmp102.read_config().unwrap().alert());
+
+        // Polarity inverts both the pin and AL
+        tmp102
+            .set_alert_polarity(AlertPolarity::ActiveHigh)
+            .unwrap();
+        assert!(!bus.borrow().alert_pin_high());
+        assert!(!tmp102.read_config().unwrap().alert());
+    }
+
+    // Interrupt mode alternates between thresholds and clears on reads
+    #[test]
+    fn test_interrupt_alert() {
+        let bus = RefCell::new(Tmp102Sim::default());
+        let mut tmp102 = TMP102::with_default_address(RefCellDevice::new(&bus));
+        tmp102
+            .set_low_threshold(Temperature::from_celsius(30))
+            .unwrap();
+        tmp102
+            .set_high_threshold(Temperature::from_celsius(35))
+            .unwrap();
+        tmp102
+            .set_thermostat_mode(ThermostatMode::Interrupt)
+            .unwrap();
+
+        let set = |celsius| {
+            bus.borrow_mut()
+                .set_temperature(Temperature::from_celsius(celsius))
+        };
+        set(36);
+        assert!(bus.borrow().alert_active());
+        tmp102.read_temperature().unwrap();
+        assert!(!bus.borrow().alert_active());
+
+        // Still hot, but now waiting for T_LOW
+        set(36);
+        assert!(!bus.borrow().alert_active());
+        set(29);
+        assert!(bus.borrow().alert_active());
+
+        // Shutdown also clears it
+        tmp102.into_shutdown().unwrap();
+        assert!(!bus.borrow().alert_active());
+    }
+
+    // The fault queue needs consecutive faults
+    #[test]
+    fn test_fault_queue() {
+        let bus = RefCell::new(Tmp102Sim::default());
+        let mut tmp102 = TMP102::with_default_address(RefCellDevice::new(&bus));
+        tmp102
+            .set_high_threshold(Temperature::from_celsius(35))
+            .unwrap();
+        tmp102.set_fault_queue(FaultQueue::Four).unwrap();
+
+        let set = |celsius| {
+            bus.borrow_mut()
+                .set_temperature(Temperature::from_celsius(celsius))
+        };
+        for celsius in [40, 40, 40, 20, 40, 40, 40] {
+            set(celsius);
+            assert!(!bus.borrow().alert_active());
+        }
+        set(40);
+        assert!(bus.borrow().alert_active());
+    }
+
+    // Every 12-bit code read over the bus decodes like the reference
+    #[test]
+    fn test_exhaustive_12_bit() {
+        let bus = RefCell::new(Tmp102Sim::default());
+        let mut tmp102 = TMP102::with_default_address(RefCellDevice::new(&bus));
+        for code in 0..4096u16 {
+            let raw = code << 4;
+            bus.borrow_mut().set_raw_temperature(raw);
+            let temp = tmp102.read_temperature().unwrap();
+            assert_eq!(
+                temp.counts() as i32,
+                decode_temperature(raw, false),
+                "code 0x{:03X}",
+                code
+            );
+        }
+    }
+
+    // Every 13-bit code read over the bus decodes like the reference
+    #[test]
+    fn test_exhaustive_13_bit() {
+        let bus = RefCell::new(Tmp102Sim::default());
+        let mut tmp102 = TMP102::with_default_address(RefCellDevice::new(&bus));
+        tmp102.set_extended_mode(true).unwrap();
+        for code in 0..8192u16 {
+            let raw = (code << 3) | 0x0001;
+            bus.borrow_mut().set_raw_temperature(raw);
+            let temp = tmp102.read_temperature().unwrap();
+            assert_eq!(
+                temp.counts() as i32,
+                decode_temperature(raw, true),
+                "code 0x{:04X}",
+                code
+            );
+        }
+    }
+
+    // Property tests against the simulator as reference
+    mod properties {
+
+        // Import the simulator and driver
+        use super::*;
+
+        // Test-only imports
+        use proptest::prelude::*;
+
+        proptest! {
+            // Any die temperature the sensor can report reads back exactly
+            #[test]
+            fn prop_driver_reads(counts in -880i16..=2400, extended: bool) {
+                let bus = RefCell::new(Tmp102Sim::default());
+                let mut tmp102 = TMP102::with_default_address(RefCellDevice::new(&bus));
+                tmp102.set_extended_mode(extended).unwrap();
+                bus.borrow_mut().set_temperature(Temperature::from_counts(counts));
+
+                let limit = if extended { 2400 } else { 2047 };
+                let temp = tmp102.read_temperature().unwrap();
+                prop_assert_eq!(temp.counts(), counts.min(limit));
+            }
+
+            // Thresholds land in the register as the sensor interprets them
+            #[test]
+            fn prop_thresholds(counts: i16, extended: bool) {
+                let bus = RefCell::new(Tmp102Sim::default());
+                let mut tmp102 = TMP102::with_default_address(RefCellDevice::new(&bus));
+                tmp102.set_extended_mode(extended).unwrap();
+                let temp = Temperature::from_counts(counts);
+                tmp102.set_low_threshold(temp).unwrap();
+                tmp102.set_high_threshold(temp).unwrap();
+
+                // Clamped to the data format, no other change
+                let (min, max) = if extended { (-4096, 4095) } else { (-2048, 2047) };
+                let expected = (counts as i32).clamp(min, max);
+                let sim = bus.borrow();
+                prop_assert_eq!(decode_temperature(sim.register(2), extended), expected);
+                prop_assert_eq!(decode_temperature(sim.register(3), extended), expected);
+                drop(sim);
+                prop_assert_eq!(tmp102.read_high_threshold().unwrap().counts() as i32, expected);
+            }
+
+            // Comparator alert follows the reference comparison
+            #[test]
+            fn prop_comparator(temp in -880i16..=2047, high in -880i16..=2047) {
+                let bus = RefCell::new(Tmp102Sim::default());
+                let mut tmp102 = TMP102::with_default_address(RefCellDevice::new(&bus));
+                tmp102.set_low_threshold(Temperature::from_counts(-880)).unwrap();
+                tmp102.set_high_threshold(Temperature::from_counts(high)).unwrap();
+                bus.borrow_mut().set_temperature(Temperature::from_counts(temp));
+                prop_assert_eq!(bus.borrow().alert_active(), temp >= high);
+            }
+        }
+    }
+}
@@ -0,0 +1,346 @@
+//! Supported sensor variants
+//!
+//! The TMP102, TMP112, TMP75 and LM75 share the same pointer register and
+//! register map (temperature, configuration, T_LOW/T_HYST and T_HIGH/T_OS)
+//! and differ in a few details captured by [`Variant`]:
+//!
+//! | Variant | Resolution | Config register | Extended mode | Conversion rate | One-shot |
+//! |---------|------------|-----------------|---------------|-----------------|----------|
+//! | TMP102  | 12-bit     | 16-bit          | yes           | yes             | yes      |
+//! | TMP112  | 12-bit     | 16-bit          | yes           | yes             | yes      |
+//! | TMP75   | 12-bit     | 8-bit           | no            | no              | yes      |
+//! | LM75    | 9-bit      | 8-bit           | no            | no              | no       |
+//!
+//! The 8-bit configuration registers match the upper byte of the TMP102
+//! layout, so the same [`Config`] type is used for all of them. Fields a
+//! variant lacks are not written and read back as their defaults.
+//!
+//! The TMP75 and LM75 have three address pins; the driver's [`crate::Address`]
+//! covers the four addresses with A2 tied low.
+
+use crate::{Config, Temperature};
+
+/// Device variant marker
+pub trait Variant: sealed::Sealed {
+    /// Part name, for log messages
+    const NAME: &'static str;
+
+    /// Temperature register resolution in normal mode (bits, left-justified)
+    const RESOLUTION_BITS: u32;
+
+    /// Width of the configuration register in bytes (2 or 1)
+    const CONFIG_BYTES: usize;
+
+    /// Whether the configuration register has the EM bit (13-bit mode)
+    const HAS_EXTENDED_MODE: bool;
+
+    /// Whether the configuration register has the CR1/CR0 bits
+    const HAS_CONVERSION_RATE: bool;
+
+    /// Maximum error over the part's best specified range (datasheet)
+    const ACCURACY: Temperature;
+
+    /// Time between conversions in continuous mode at power-on
+    ///
+    /// Variants without CR1/CR0 convert back to back at this rate.
+    const CONVERSION_PERIOD_US: u32;
+
+    /// Decode the configuration register (8-bit registers in the upper byte)
+    #[doc(hidden)]
+    fn decode_config(raw: u16) -> Option<Config>;
+
+    /// Encode a configuration (8-bit registers in the upper byte)
+    #[doc(hidden)]
+    fn encode_config(config: Config) -> u16;
+}
+
+/// Variant with a 13-bit extended mode (EM bit)
+pub trait ExtendedMode: Variant {}
+
+/// Variant that can run single conversions from shutdown (OS bit)
+pub trait OneShot: Variant {
+    /// Typical conversion time at full resolution
+    const CONVERSION_TIME_MS: u32;
+
+    /// Maximum conversion time at full resolution
+    const CONVERSION_TIME_MAX_MS: u32;
+
+    /// Whether OS reads back 1 once a conversion has finished
+    const READY_FLAG: bool;
+}
+
+/// Variant whose OS bit reports when a one-shot conversion is done
+pub trait ConversionReady: OneShot {}
+
+/// Texas Instruments TMP102 (±2 °C max from -25 °C to 85 °C)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tmp102;
+
+/// Texas Instruments TMP112 (factory calibrated, ±0.5 °C max from 0 °C to 65 °C)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tmp112;
+
+/// Texas Instruments TMP75 (±1 °C max from -25 °C to 85 °C)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tmp75;
+
+/// LM75 and compatible parts (±2 °C max from -25 °C to 100 °C)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lm75;
+
+impl Variant for Tmp102 {
+    const NAME: &'static str = "TMP102";
+    const RESOLUTION_BITS: u32 = 12;
+    const CONFIG_BYTES: usize = 2;
+    const HAS_EXTENDED_MODE: bool = true;
+    const HAS_CONVERSION_RATE: bool = true;
+    const ACCURACY: Temperature = Temperature::from_celsius(2);
+    const CONVERSION_PERIOD_US: u32 = 250_000;
+
+    fn decode_config(raw: u16) -> Option<Config> {
+        Config::try_from_bits(raw)
+    }
+
+    fn encode_config(config: Config) -> u16 {
+        config.to_bits()
+    }
+}
+
+impl ExtendedMode for Tmp102 {}
+
+impl OneShot for Tmp102 {
+    const CONVERSION_TIME_MS: u32 = 26;
+    const CONVERSION_TIME_MAX_MS: u32 = 35;
+    const READY_FLAG: bool = true;
+}
+
+impl ConversionReady for Tmp102 {}
+
+impl Variant for Tmp112 {
+    const NAME: &'static str = "TMP112";
+    const RESOLUTION_BITS: u32 = 12;
+    const CONFIG_BYTES: usize = 2;
+    const HAS_EXTENDED_MODE: bool = true;
+    const HAS_CONVERSION_RATE: bool = true;
+    const ACCURACY: Temperature = Temperature::from_millicelsius(500);
+    const CONVERSION_PERIOD_US: u32 = 250_000;
+
+    fn decode_config(raw: u16) -> Option<Config> {
+        Config::try_from_bits(raw)
+    }
+
+    fn encode_config(config: Config) -> u16 {
+        config.to_bits()
+    }
+}
+
+impl ExtendedMode for Tmp112 {}
+
+impl OneShot for Tmp112 {
+    const CONVERSION_TIME_MS: u32 = 26;
+    const CONVERSION_TIME_MAX_MS: u32 = 35;
+    const READY_FLAG: bool = true;
+}
+
+impl ConversionReady for Tmp112 {}
+
+impl Variant for Tmp75 {
+    const NAME: &'static str = "TMP75";
+    const RESOLUTION_BITS: u32 = 12;
+    const CONFIG_BYTES: usize = 1;
+    const HAS_EXTENDED_MODE: bool = false;
+    const HAS_CONVERSION_RATE: bool = false;
+    const ACCURACY: Temperature = Temperature::from_celsius(1);
+    const CONVERSION_PERIOD_US: u32 = 220_000;
+
+    /// Bit 7 reads back as the comparator status (OS/ALERT), not OS
+    fn decode_config(raw: u16) -> Option<Config> {
+        let alert = raw & 0x8000 != 0;
+        Some(from_upper_byte(raw).with_alert(alert))
+    }
+
+    /// R1/R0 are written as 11 (12-bit resolution)
+    fn encode_config(config: Config) -> u16 {
+        config.to_bits() & 0xFF00
+    }
+}
+
+impl OneShot for Tmp75 {
+    const CONVERSION_TIME_MS: u32 = 220;
+    const CONVERSION_TIME_MAX_MS: u32 = 300;
+    const READY_FLAG: bool = false;
+}
+
+impl Variant for Lm75 {
+    const NAME: &'static str = "LM75";
+    const RESOLUTION_BITS: u32 = 9;
+    const CONFIG_BYTES: usize = 1;
+    const HAS_EXTENDED_MODE: bool = false;
+    const HAS_CONVERSION_RATE: bool = false;
+    const ACCURACY: Temperature = Temperature::from_celsius(2);
+    const CONVERSION_PERIOD_US: u32 = 100_000;
+
+    /// Bits 7..5 are reserved and read as 0; there is no alert status bit
+    fn decode_config(raw: u16) -> Option<Config> {
+        if raw & 0xE000 != 0 {
+            return None;
+        }
+        Some(from_upper_byte(raw))
+    }
+
+    fn encode_config(config: Config) -> u16 {
+        config.to_bits() & 0x1F00
+    }
+}
+
+/// Decode an 8-bit configuration register, defaulting the lower byte
+fn from_upper_byte(raw: u16) -> Config {
+    let lower = Config::default().to_bits() & 0x00FF;
+    Config::from_bits((raw & 0xFF00) | lower).with_one_shot(false)
+}
+
+/// Decode a temperature or threshold register at the variant's resolution
+pub(crate) fn decode<V: Variant>(raw: u16, extended_mode: bool) -> Temperature {
+    from_register(raw & data_mask::<V>(extended_mode), extended_mode)
+}
+
+/// Encode a threshold at the variant's resolution (rounding down)
+pub(crate) fn encode<V: Variant>(temp: Temperature, extended_mode: bool) -> u16 {
+    to_register(temp, extended_mode) & data_mask::<V>(extended_mode)
+}
+
+/// Decode a temperature or threshold register (left-justified data)
+fn from_register(raw: u16, extended_mode: bool) -> Temperature {
+    if extended_mode {
+        Temperature::from_counts((raw as i16) >> 3)
+    } else {
+        Temperature::from_counts((raw as i16) >> 4)
+    }
+}
+
+/// Encode for a threshold register, clamping to the 12- or 13-bit range
+fn to_register(temp: Temperature, extended_mode: bool) -> u16 {
+    if extended_mode {
+        (temp.counts().clamp(-4096, 4095) << 3) as u16
+    } else {
+        (temp.counts().clamp(-2048, 2047) << 4) as u16
+    }
+}
+
+/// Bits of the temperature register that hold data
+fn data_mask<V: Variant>(extended_mode: bool) -> u16 {
+    let bits = if extended_mode {
+        13
+    } else {
+        V::RESOLUTION_BITS
+    };
+    u16::MAX << (16 - bits)
+}
+
+/// Keep other crates from adding variants the driver cannot handle
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for super::Tmp102 {}
+    impl Sealed for super::Tmp112 {}
+    impl Sealed for super::Tmp75 {}
+    impl Sealed for super::Lm75 {}
+}
+
+#[cfg(test)]
+mod tests {
+
+    // Import top-level structs/functions
+    use super::*;
+
+    // Test-only imports
+    use crate::{AlertPolarity, ConversionRate, FaultQueue, ThermostatMode};
+
+    // LM75 drops the bits below its 0.5 °C resolution
+    #[test]
+    fn test_resolution() {
+        assert_eq!(decode::<Tmp102>(0x1910, false).counts(), 401);
+        assert_eq!(decode::<Lm75>(0x1910, false).counts(), 400);
+        assert_eq!(
+            decode::<Lm75>(0xE780, false),
+            Temperature::from_millicelsius(-24_500)
+        );
+        assert_eq!(encode::<Lm75>(Temperature::from_counts(401), false), 0x1900);
+        assert_eq!(
+            encode::<Tmp102>(Temperature::from_counts(401), true),
+            0x0C88
+        );
+    }
+
+    // 8-bit registers match the upper byte of the 16-bit layout
+    #[test]
+    fn test_config_bytes() {
+        let config = Config::default()
+            .with_shutdown(true)
+            .with_thermostat_mode(ThermostatMode::Interrupt)
+            .with_alert_polarity(AlertPolarity::ActiveHigh)
+            .with_fault_queue(FaultQueue::Six)
+            .with_conversion_rate(ConversionRate::Hz8)
+            .with_extended_mode(true);
+        assert_eq!(Tmp102::encode_config(config), 0x7FF0);
+        assert_eq!(Tmp112::encode_config(config), 0x7FF0);
+        assert_eq!(Tmp75::encode_config(config), 0x7F00);
+        assert_eq!(Lm75::encode_config(config), 0x1F00);
+
+        // Missing fields decode as defaults
+        let decoded = Lm75::decode_config(0x1F00).unwrap();
+        assert_eq!(decoded.fault_queue(), FaultQueue::Six);
+        assert!(decoded.shutdown());
+        assert_eq!(decoded.conversion_rate(), ConversionRate::Hz4);
+        assert!(!decoded.extended_mode());
+    }
+
+    // Each variant rejects impossible register contents its own way
+    #[test]
+    fn test_config_validation() {
+        assert!(Tmp102::decode_config(0xFFFF).is_none());
+        assert!(Lm75::decode_config(0xFF00).is_none());
+
+        // TMP75 reports the comparator status in bit 7
+        let config = Tmp75::decode_config(0x8000).unwrap();
+        assert!(config.alert());
+        assert!(!config.one_shot());
+        assert!(!Tmp75::decode_config(0x6000).unwrap().alert());
+    }
+
+    // Property tests, with the simulator's encoding as reference
+    mod properties {
+
+        // Import top-level structs/functions
+        use super::*;
+
+        // Test-only imports
+        use crate::sim::{decode_temperature, encode_temperature};
+        use proptest::prelude::*;
+
+        proptest! {
+            // Register encode/decode round-trips within each data format
+            #[test]
+            fn prop_register_round_trip(counts in -4096i16..=4095) {
+                let temp = Temperature::from_counts(counts);
+                prop_assert_eq!(from_register(to_register(temp, true), true), temp);
+                let normal = Temperature::from_counts(counts.clamp(-2048, 2047));
+                prop_assert_eq!(from_register(to_register(temp, false), false), normal);
+            }
+
+            // Decoding any register agrees with the reference
+            #[test]
+            fn prop_decode_matches_reference(raw: u16, extended: bool) {
+                let temp = from_register(raw, extended);
+                prop_assert_eq!(temp.counts() as i32, decode_temperature(raw, extended));
+            }
+
+            // Encoding agrees with the reference inside the sensor's range
+            #[test]
+            fn prop_encode_matches_reference(counts in -880i16..=2047, extended: bool) {
+                let temp = Temperature::from_counts(counts);
+                let reference = encode_temperature(temp, extended) & !0x0001;
+                prop_assert_eq!(to_register(temp, extended), reference);
+            }
+        }
+    }
+}